@@ -0,0 +1,232 @@
+//! Synchronous API for applications not using tokio
+//!
+//! # Example
+//! ```no_run
+//! use rfxtrx433::{blocking, Protocols1, Protocols2, Protocols3, Protocols4};
+//!
+//! fn main() -> rfxtrx433::Result<()> {
+//!     let mut rfx = blocking::RFXtrx433::new_from_serial_number("123ABC")?;
+//!     rfx.reset()?;
+//!     rfx.start_receiver()?;
+//!     rfx.set_mode(
+//!         Default::default(),
+//!         Protocols1::FINEOFFSET,
+//!         Protocols2::empty(),
+//!         Protocols3::empty(),
+//!         Protocols4::empty(),
+//!     )?;
+//!     loop {
+//!         println!("Received message {:?}", rfx.read_message()?);
+//!     }
+//! }
+//! ```
+use crate::{
+    trx_command::{
+        Frequency, Lighting1Command, Lighting1SubType, Lighting2Command, Lighting2SubType,
+        Lighting3Command, Lighting5SubType, Lighting6Command, Lighting6SubType, TransmitResult,
+    },
+    HouseCode, Protocols1, Protocols2, Protocols3, Protocols4, RFXtrx433Builder, RFXtrx433Info,
+    ReceivedMessage, RemoteId, Result, UnitCode,
+};
+use std::{future::Future, time::Duration};
+use tokio::runtime::{Builder, Runtime};
+
+/// Blocking wrapper around `rfxtrx433::RFXtrx433`.
+///
+/// The connection is driven by an internal current-thread runtime, which only runs
+/// while a method is blocking. Messages received in between are read into the
+/// message queue the next time a method blocks. When the queue is full new
+/// messages are dropped and `ConnectionEvent::QueueOverflow` is sent, so raise
+/// `RFXtrx433Builder::queue_len` if messages arrive in bursts between
+/// `read_message` calls.
+pub struct RFXtrx433 {
+    inner: crate::RFXtrx433,
+    rt: Runtime,
+}
+
+impl RFXtrx433 {
+    /// Try to create an instance from a serial number,
+    /// see `rfxtrx433::RFXtrx433::new_from_serial_number`
+    pub fn new_from_serial_number(serial: &str) -> Result<Self> {
+        Self::open(|builder| builder.open_serial_number(serial))
+    }
+
+    /// Create an instance from a serial port tty, e.g. /dev/ttyUSB0
+    pub fn new_from_serial_port(port: &str) -> Result<Self> {
+        Self::open(|builder| builder.open_serial_port(port))
+    }
+
+    /// Create an instance from a TCP address, e.g. a device shared with ser2net
+    pub fn new_from_tcp(addr: &str) -> Result<Self> {
+        Self::open(|builder| builder.open_tcp(addr))
+    }
+
+    /// Create an instance with non default settings, e.g.
+    /// `open_with(RFXtrx433::builder().baud_rate(9600), |b| b.open_serial_port(port))`
+    pub fn open_with<F, Fut>(builder: RFXtrx433Builder, open: F) -> Result<Self>
+    where
+        F: FnOnce(RFXtrx433Builder) -> Fut,
+        Fut: Future<Output = Result<crate::RFXtrx433>>,
+    {
+        let rt = Builder::new_current_thread().enable_all().build()?;
+        let inner = rt.block_on(open(builder))?;
+        Ok(RFXtrx433 { inner, rt })
+    }
+
+    fn open<F, Fut>(open: F) -> Result<Self>
+    where
+        F: FnOnce(RFXtrx433Builder) -> Fut,
+        Fut: Future<Output = Result<crate::RFXtrx433>>,
+    {
+        Self::open_with(crate::RFXtrx433::builder(), open)
+    }
+
+    /// Closes the connection, see `rfxtrx433::Sender::close`
+    pub fn close(self) -> Result<()> {
+        self.rt.block_on(self.inner.close())
+    }
+
+    /// Sends a reset signal to the device
+    pub fn reset(&mut self) -> Result<()> {
+        self.rt.block_on(self.inner.reset())
+    }
+
+    /// Sends a get status signal to the device and waits for a response
+    pub fn get_status(&mut self) -> Result<RFXtrx433Info> {
+        self.rt.block_on(self.inner.get_status())
+    }
+
+    /// Starts the receiver, returning the copyright message sent by the device
+    pub fn start_receiver(&mut self) -> Result<String> {
+        self.rt.block_on(self.inner.start_receiver())
+    }
+
+    /// Sets the mode of the receiver, then calls save.
+    pub fn set_mode(
+        &mut self,
+        frequency: Frequency,
+        protos_1: Protocols1,
+        protos_2: Protocols2,
+        protos_3: Protocols3,
+        protos_4: Protocols4,
+    ) -> Result<()> {
+        self.rt.block_on(
+            self.inner
+                .set_mode(frequency, protos_1, protos_2, protos_3, protos_4),
+        )
+    }
+
+    /// Waits for protocol messages from the device
+    pub fn read_message(&mut self) -> Result<ReceivedMessage> {
+        self.rt.block_on(self.inner.read_message())
+    }
+
+    /// Waits for a protocol message from the device, or returns `TRXError::Timeout`
+    /// if none is received within `timeout`.
+    pub fn read_message_timeout(&mut self, timeout: Duration) -> Result<ReceivedMessage> {
+        self.rt.block_on(self.inner.read_message_timeout(timeout))
+    }
+
+    /// Sends a Lighting1 command, see `rfxtrx433::Sender::send_lighting1`
+    pub fn send_lighting1(
+        &mut self,
+        sub_type: Lighting1SubType,
+        house_code: HouseCode,
+        unit_code: UnitCode,
+        command: Lighting1Command,
+    ) -> Result<TransmitResult> {
+        self.rt.block_on(
+            self.inner
+                .send_lighting1(sub_type, house_code, unit_code, command),
+        )
+    }
+
+    /// Sends a Lighting2 command, see `rfxtrx433::Sender::send_lighting2`
+    pub fn send_lighting2(
+        &mut self,
+        sub_type: Lighting2SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: Lighting2Command,
+        level: u8,
+    ) -> Result<TransmitResult> {
+        self.rt.block_on(
+            self.inner
+                .send_lighting2(sub_type, id, unit_code, command, level),
+        )
+    }
+
+    /// Sends a Lighting3 command, see `rfxtrx433::Sender::send_lighting3`
+    pub fn send_lighting3(
+        &mut self,
+        system: RemoteId,
+        channels: u16,
+        command: Lighting3Command,
+    ) -> Result<TransmitResult> {
+        self.rt
+            .block_on(self.inner.send_lighting3(system, channels, command))
+    }
+
+    /// Sends a Lighting5 command, see `rfxtrx433::Sender::send_lighting5`
+    pub fn send_lighting5(
+        &mut self,
+        sub_type: Lighting5SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: u8,
+        level: u8,
+    ) -> Result<TransmitResult> {
+        self.rt.block_on(
+            self.inner
+                .send_lighting5(sub_type, id, unit_code, command, level),
+        )
+    }
+
+    /// Sends a Lighting6 command, see `rfxtrx433::Sender::send_lighting6`
+    pub fn send_lighting6(
+        &mut self,
+        sub_type: Lighting6SubType,
+        id: RemoteId,
+        group_code: HouseCode,
+        unit_code: UnitCode,
+        command: Lighting6Command,
+    ) -> Result<TransmitResult> {
+        self.rt.block_on(
+            self.inner
+                .send_lighting6(sub_type, id, group_code, unit_code, command),
+        )
+    }
+
+    /// Sends a packet the library doesn't model, see `rfxtrx433::Sender::send_raw`
+    pub fn send_raw(&mut self, packet_type: u8, sub_type: u8, payload: &[u8]) -> Result<()> {
+        self.inner.send_raw(packet_type, sub_type, payload)
+    }
+
+    /// Runs `f` with the async device, for the commands not wrapped here
+    pub fn run<T>(
+        &mut self,
+        f: impl FnOnce(&mut crate::RFXtrx433) -> std::pin::Pin<Box<dyn Future<Output = T> + '_>>,
+    ) -> T {
+        self.rt.block_on(f(&mut self.inner))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TRXError;
+
+    #[test]
+    fn blocking_read_timeout() {
+        let (transport, _device) = tokio::io::duplex(1024);
+        let mut rfx =
+            RFXtrx433::open_with(crate::RFXtrx433::builder(), |b| b.open_transport(transport))
+                .unwrap();
+        let res = rfx.read_message_timeout(Duration::from_millis(10));
+        assert!(matches!(res, Err(TRXError::Timeout)));
+        rfx.send_raw(0x11, 0x00, &[0x01, 0x02]).unwrap();
+        let res = rfx.run(|r| Box::pin(r.read_message_timeout(Duration::from_millis(10))));
+        assert!(matches!(res, Err(TRXError::Timeout)));
+        rfx.close().unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    find_port, list_candidate_ports, runtime, DeviceRegistry, DutyCycleOptions, Port, RFXtrx433,
+    ReconnectOptions, RepeatPolicy, Result, TRXError, TemperatureUnit, Transport,
+    DEFAULT_COMMAND_TIMEOUT, MESSAGE_QUEUE_LEN,
+};
+use log::debug;
+use std::path::{Path, PathBuf};
+use tokio::time::Duration;
+
+/// How often the serial ports are checked while waiting for a device
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Creates a `RFXtrx433` with non default settings, see `RFXtrx433::builder`
+#[derive(Clone, Debug)]
+pub struct RFXtrx433Builder {
+    pub(crate) baud_rate: u32,
+    pub(crate) command_timeout: Duration,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) queue_len: usize,
+    pub(crate) auto_reset: bool,
+    pub(crate) reconnect: Option<ReconnectOptions>,
+    pub(crate) record: Option<PathBuf>,
+    pub(crate) temperature_unit: TemperatureUnit,
+    pub(crate) registry: Option<DeviceRegistry>,
+    pub(crate) repeat: RepeatPolicy,
+    pub(crate) dedup: Option<Duration>,
+    pub(crate) duty_cycle: Option<DutyCycleOptions>,
+}
+
+impl Default for RFXtrx433Builder {
+    fn default() -> Self {
+        RFXtrx433Builder {
+            baud_rate: 38400,
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+            read_timeout: None,
+            queue_len: MESSAGE_QUEUE_LEN,
+            auto_reset: false,
+            reconnect: None,
+            record: None,
+            temperature_unit: TemperatureUnit::Celsius,
+            registry: None,
+            repeat: RepeatPolicy::default(),
+            dedup: None,
+            duty_cycle: None,
+        }
+    }
+}
+
+impl RFXtrx433Builder {
+    /// Baud rate of the serial port, the default is 38400
+    pub fn baud_rate(mut self, baud_rate: u32) -> Self {
+        self.baud_rate = baud_rate;
+        self
+    }
+
+    /// Time to wait for the device to respond to a command, the default is 5 seconds
+    pub fn command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    /// Time `read_message` waits for a message before returning `TRXError::Timeout`,
+    /// by default it waits forever
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Number of messages queued before new ones are dropped, the default is 100.
+    /// Opening fails with `TRXError::InvalidArgument` if it is 0.
+    pub fn queue_len(mut self, queue_len: usize) -> Self {
+        self.queue_len = queue_len;
+        self
+    }
+
+    /// Resets the device after connecting
+    pub fn auto_reset(mut self, auto_reset: bool) -> Self {
+        self.auto_reset = auto_reset;
+        self
+    }
+
+    /// Reopens the serial port with backoff if it fails, e.g. because the USB device
+    /// glitched. After reopening, the device is reset and the last set mode and start
+    /// receiver are replayed.
+    ///
+    /// Use `Sender::connection_events` to follow the reconnects.
+    pub fn reconnect(mut self, options: ReconnectOptions) -> Self {
+        self.reconnect = Some(options);
+        self
+    }
+
+    /// Records every frame sent and received to the file at `path`,
+    /// it can be played back with `ReplayTransport`.
+    pub fn record(mut self, path: impl AsRef<Path>) -> Self {
+        self.record = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Unit of `ReceivedMessage::temperature`, the default is Celsius. The temperature
+    /// fields of the messages are always in Celsius.
+    pub fn temperature_unit(mut self, unit: TemperatureUnit) -> Self {
+        self.temperature_unit = unit;
+        self
+    }
+
+    /// Records the devices heard in `registry`, keep a clone to query it
+    pub fn device_registry(mut self, registry: DeviceRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// How many times radio commands are sent, see `Sender::set_repeat`
+    pub fn repeat(mut self, policy: RepeatPolicy) -> Self {
+        self.repeat = policy;
+        self
+    }
+
+    /// Drops messages repeating the last one of the same device within `window`, so a
+    /// button press that the remote sends several times is read once. The window is
+    /// counted from the last repeat, 500 ms covers most remotes.
+    ///
+    /// The device registry still records every message.
+    pub fn dedup(mut self, window: Duration) -> Self {
+        self.dedup = Some(window);
+        self
+    }
+
+    /// Limits the time spent transmitting to the duty cycle allowed in the band.
+    /// Commands wait until they can be sent, or are rejected, see `DutyCycleOptions`.
+    /// Opening fails with `TRXError::InvalidArgument` if the options are invalid.
+    pub fn duty_cycle(mut self, options: DutyCycleOptions) -> Self {
+        self.duty_cycle = Some(options);
+        self
+    }
+
+    /// Connects to the device with a serial number, the available serial ports
+    /// are searched for it.
+    pub async fn open_serial_number(self, serial: &str) -> Result<RFXtrx433> {
+        self.open(Port::SerialNumber(serial.to_string())).await
+    }
+
+    /// Waits until a device with the serial number is plugged in, then connects to it.
+    /// Returns `TRXError::Timeout` if it doesn't appear within `timeout`.
+    pub async fn wait_for_serial_number(
+        self,
+        serial: &str,
+        timeout: Duration,
+    ) -> Result<RFXtrx433> {
+        runtime::timeout(timeout, async {
+            while let Err(TRXError::DeviceWithSerialNotFound(_)) = find_port(serial) {
+                runtime::sleep(DEVICE_POLL_INTERVAL).await;
+            }
+        })
+        .await?;
+        self.open_serial_number(serial).await
+    }
+
+    /// Connects to the device plugged in at a physical USB port, for devices that
+    /// don't report a serial number. `bus` is the bus id and `port_chain` the
+    /// hub ports leading to the device, e.g. "1" and [2, 4] for 1-2.4 in sysfs.
+    pub async fn open_usb_path(self, bus: &str, port_chain: &[u8]) -> Result<RFXtrx433> {
+        let location = serialport::Location::new(bus.to_string(), port_chain.to_vec());
+        self.open(Port::UsbPath(location)).await
+    }
+
+    /// Connects to the device on a serial port tty, e.g. /dev/ttyUSB0
+    pub async fn open_serial_port(self, port: &str) -> Result<RFXtrx433> {
+        self.open(Port::Path(port.to_string())).await
+    }
+
+    /// Connects to the first port from `list_candidate_ports` where the device
+    /// answers a status request.
+    pub async fn open_auto(self) -> Result<RFXtrx433> {
+        for candidate in list_candidate_ports()? {
+            let port = Port::Path(candidate.port_name);
+            debug!("Probing {:?}", port);
+            let mut rfx = match self.clone().open(port).await {
+                Ok(rfx) => rfx,
+                Err(e) => {
+                    debug!("Failed to open candidate port: {}", e);
+                    continue;
+                }
+            };
+            match rfx.get_status().await {
+                Ok(_) => return Ok(rfx),
+                Err(e) => {
+                    debug!("No status from candidate port: {}", e);
+                    let _ = rfx.close().await;
+                }
+            }
+        }
+        Err(TRXError::NoDeviceFound)
+    }
+
+    /// Connects to the device over TCP, e.g. a device shared with ser2net.
+    /// The baud rate is set by the remote end.
+    pub async fn open_tcp(self, addr: &str) -> Result<RFXtrx433> {
+        self.open(Port::Tcp(addr.to_string())).await
+    }
+
+    /// Connects to the device over `transport`, e.g. a PTY or a bridge.
+    ///
+    /// The transport can't be reopened, so `reconnect` isn't supported.
+    pub async fn open_transport(self, transport: impl Transport) -> Result<RFXtrx433> {
+        let rfx = RFXtrx433::open(&self, Box::new(transport), None)?;
+        self.connected(rfx).await
+    }
+
+    async fn open(self, port: Port) -> Result<RFXtrx433> {
+        debug!("Opening {:?}", port);
+        let transport = port.open(self.baud_rate).await?;
+        let rfx = RFXtrx433::open(&self, transport, Some(port))?;
+        self.connected(rfx).await
+    }
+
+    async fn connected(&self, mut rfx: RFXtrx433) -> Result<RFXtrx433> {
+        if self.auto_reset {
+            rfx.reset().await?;
+        }
+        Ok(rfx)
+    }
+}
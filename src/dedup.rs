@@ -0,0 +1,76 @@
+//! Dropping of the repeated frames remotes send for every button press
+use crate::{DeviceId, ReceivedMessage};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Remembers the last frame of every device, see `RFXtrx433Builder::dedup`
+#[derive(Debug)]
+pub(crate) struct Deduplicator {
+    window: Duration,
+    // Payload of the last frame of each device and when it was received
+    last: HashMap<DeviceId, (Vec<u8>, Instant)>,
+}
+
+impl Deduplicator {
+    pub(crate) fn new(window: Duration) -> Self {
+        Deduplicator {
+            window,
+            last: HashMap::new(),
+        }
+    }
+
+    /// Returns true if `msg` repeats the last frame of the device within the window.
+    /// Messages without a device id are never repeats.
+    pub(crate) fn is_repeat(&mut self, msg: &ReceivedMessage) -> bool {
+        let id = match msg.device_id() {
+            Some(id) => id,
+            None => return false,
+        };
+        let window = self.window;
+        self.last
+            .retain(|_, (_, instant)| msg.instant.duration_since(*instant) < window);
+        let payload = payload(&msg.raw);
+        match self.last.get_mut(&id) {
+            Some((last, instant)) if last.as_slice() == payload => {
+                // A burst is one press however long it lasts
+                *instant = msg.instant;
+                true
+            }
+            _ => {
+                self.last.insert(id, (payload.to_vec(), msg.instant));
+                false
+            }
+        }
+    }
+}
+
+/// The frame without the length, packet type, sub type and sequence number, and
+/// the last byte with the signal strength that varies between the repeats
+fn payload(frame: &[u8]) -> &[u8] {
+    frame.get(4..frame.len().saturating_sub(1)).unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::parse_frame;
+
+    #[test]
+    fn dedup() {
+        let mut dedup = Deduplicator::new(Duration::from_millis(500));
+        let on = parse_frame(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70]).unwrap();
+        let mut repeat = parse_frame(&[0x07, 0x10, 0x01, 0x01, 0x42, 0x05, 0x01, 0x50]).unwrap();
+        repeat.instant = on.instant + Duration::from_millis(100);
+        let mut off = parse_frame(&[0x07, 0x10, 0x01, 0x02, 0x42, 0x05, 0x00, 0x70]).unwrap();
+        off.instant = on.instant + Duration::from_millis(200);
+        let mut on_again = on.clone();
+        on_again.instant = on.instant + Duration::from_secs(1);
+
+        assert!(!dedup.is_repeat(&on));
+        assert!(dedup.is_repeat(&repeat));
+        assert!(!dedup.is_repeat(&off));
+        assert!(!dedup.is_repeat(&on_again));
+    }
+}
@@ -0,0 +1,384 @@
+//! Concise, human readable formatting of the protocol messages for logs and CLI output,
+//! e.g. "TempHum id=0x2F01 21.3°C 45% batt=ok rssi=6". Temperatures are shown in
+//! Celsius.
+use crate::trx_command::{
+    AsyncData, AsyncPort, BatteryStatus, Bbq, Camera, Curtain, Energy, Fan, Funkbus, HomeConfort,
+    Hum, HunterFan, Lighting1, Lighting2, Lighting4, Power, ProtocolMessage, RawPulses,
+    RemoteControl, Rssi, Security1, Security2, Temp, TempHum, TempHumBaro, TempRain, Undecoded,
+    WeatherStation, Weight,
+};
+use std::fmt;
+
+impl fmt::Display for ProtocolMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolMessage::Undecoded(msg) => msg.fmt(f),
+            ProtocolMessage::Lighting1(msg) => msg.fmt(f),
+            ProtocolMessage::Lighting2(msg) => msg.fmt(f),
+            ProtocolMessage::Lighting4(msg) => msg.fmt(f),
+            ProtocolMessage::Fan(msg) => msg.fmt(f),
+            ProtocolMessage::Curtain(msg) => msg.fmt(f),
+            ProtocolMessage::HomeConfort(msg) => msg.fmt(f),
+            ProtocolMessage::Funkbus(msg) => msg.fmt(f),
+            ProtocolMessage::HunterFan(msg) => msg.fmt(f),
+            ProtocolMessage::Security1(msg) => msg.fmt(f),
+            ProtocolMessage::Security2(msg) => msg.fmt(f),
+            ProtocolMessage::Camera(msg) => msg.fmt(f),
+            ProtocolMessage::RemoteControl(msg) => msg.fmt(f),
+            ProtocolMessage::Bbq(msg) => msg.fmt(f),
+            ProtocolMessage::TempRain(msg) => msg.fmt(f),
+            ProtocolMessage::Temp(msg) => msg.fmt(f),
+            ProtocolMessage::Hum(msg) => msg.fmt(f),
+            ProtocolMessage::TempHum(msg) => msg.fmt(f),
+            ProtocolMessage::TempHumBaro(msg) => msg.fmt(f),
+            ProtocolMessage::Energy(msg) => msg.fmt(f),
+            ProtocolMessage::Power(msg) => msg.fmt(f),
+            ProtocolMessage::Weight(msg) => msg.fmt(f),
+            ProtocolMessage::AsyncPort(msg) => msg.fmt(f),
+            ProtocolMessage::AsyncData(msg) => msg.fmt(f),
+            ProtocolMessage::WeatherStation(msg) => msg.fmt(f),
+            ProtocolMessage::RawPulses(msg) => msg.fmt(f),
+            ProtocolMessage::NotParsed { header, data } => write!(
+                f,
+                "NotParsed {:?} sub_type=0x{:02X} {:02X?}",
+                header.packet_type(),
+                header.sub_type(),
+                data
+            ),
+        }
+    }
+}
+
+impl fmt::Display for BatteryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatteryStatus::Low => write!(f, "low"),
+            BatteryStatus::Level(_) => write!(f, "{}%", self.percentage()),
+            BatteryStatus::Ok => write!(f, "ok"),
+        }
+    }
+}
+
+impl fmt::Display for Rssi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+impl fmt::Display for Undecoded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Undecoded {:?} {:02X?}", self.protocol, self.data)
+    }
+}
+
+impl fmt::Display for Lighting1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Lighting1 {:?} {}{} {:?} rssi={}",
+            self.sub_type, self.house_code, self.unit_code, self.command, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Lighting2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Lighting2 {:?} id=0x{} unit={} {:?} level={} rssi={}",
+            self.sub_type, self.id, self.unit_code, self.command, self.level, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Lighting4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Lighting4 code=0x{:06X} pulse={}us rssi={}",
+            self.code, self.pulse, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Fan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Fan {:?} id=0x{} cmd=0x{:02X} rssi={}",
+            self.sub_type, self.id, self.command, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Curtain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Curtain {}{} {:?} rssi={}",
+            self.house_code, self.unit_code, self.command, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for HomeConfort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HomeConfort id=0x{} {}{} {:?} rssi={}",
+            self.id, self.house_code, self.unit_code, self.command, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Funkbus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Funkbus {:?} id=0x{} {}{} {:?} time={} rssi={}",
+            self.sub_type,
+            self.id,
+            self.group,
+            self.unit_code,
+            self.command,
+            self.command_time,
+            self.rssi
+        )
+    }
+}
+
+impl fmt::Display for HunterFan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HunterFan id=0x")?;
+        for b in &self.id {
+            write!(f, "{:02X}", b)?;
+        }
+        write!(f, " cmd=0x{:02X} rssi={}", self.command, self.rssi)
+    }
+}
+
+impl fmt::Display for Security1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Security1 {:?} id=0x{} {:?}{} batt={} rssi={}",
+            self.sub_type,
+            self.id,
+            self.status,
+            if self.tamper { " tamper" } else { "" },
+            self.battery,
+            self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Security2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Security2 id=0x{} buttons=0b{:04b} batt={} rssi={}",
+            self.id, self.buttons, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Camera {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Camera {} {:?} rssi={}",
+            self.house_code, self.command, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for RemoteControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RemoteControl {:?} id=0x{:02X} cmd=0x{:02X} rssi={}",
+            self.sub_type,
+            self.id.value(),
+            self.command,
+            self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Bbq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Bbq id=0x{} food={:.1}°C bbq={:.1}°C batt={} rssi={}",
+            self.id, self.food_temp, self.bbq_temp, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for TempRain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TempRain id=0x{} {:.1}°C rain={:.1}mm batt={} rssi={}",
+            self.id, self.temp, self.rain_total, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Temp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Temp id=0x{} {:.1}°C batt={} rssi={}",
+            self.id, self.temp, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Hum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Hum id=0x{} {}% batt={} rssi={}",
+            self.id, self.humidity, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for TempHum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TempHum id=0x{} {:.1}°C {}% batt={} rssi={}",
+            self.id, self.temp, self.humidity, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for TempHumBaro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TempHumBaro id=0x{} {:.1}°C {}% {}hPa {:?} batt={} rssi={}",
+            self.id, self.temp, self.humidity, self.baro, self.forecast, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Energy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Energy id=0x{} {}W total={:.3}kWh batt={} rssi={}",
+            self.id, self.instant_power, self.total_usage, self.battery, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Power {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Power id=0x{} {}V {:.2}A {:.1}W total={:.2}kWh pf={:.2} {}Hz rssi={}",
+            self.id,
+            self.voltage,
+            self.current,
+            self.power,
+            self.energy,
+            self.power_factor,
+            self.frequency,
+            self.rssi
+        )
+    }
+}
+
+impl fmt::Display for Weight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Weight id=0x{} {:.1}kg rssi={}",
+            self.id, self.weight, self.rssi
+        )
+    }
+}
+
+impl fmt::Display for AsyncPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AsyncPort {:?} {:?} {:?} {}{}",
+            self.command,
+            self.baud_rate,
+            self.parity,
+            self.data_bits,
+            if self.inverted { " inverted" } else { "" }
+        )
+    }
+}
+
+impl fmt::Display for AsyncData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AsyncData {:?} seq={} {:02X?}",
+            self.sub_type, self.seqnbr, self.data
+        )
+    }
+}
+
+impl fmt::Display for WeatherStation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "WeatherStation id=0x{} {:.1}°C {}% wind={}° {:.1}m/s gust={:.1}m/s \
+             rain={:.1}mm {}hPa batt={} rssi={}",
+            self.id,
+            self.temp,
+            self.humidity,
+            self.wind_direction,
+            self.wind_speed,
+            self.wind_gust,
+            self.rain_total,
+            self.baro,
+            self.battery,
+            self.rssi
+        )
+    }
+}
+
+impl fmt::Display for RawPulses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RawPulses frame={} {} pulses",
+            self.sub_type,
+            self.pulses.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::trx_command::{parse_message, ReceivedCommand};
+
+    #[test]
+    fn display_messages() {
+        let msg =
+            parse_message(&[0x52, 0x01, 0x00, 0x2F, 0x01, 0x00, 0xD5, 0x2D, 0x01, 0x69]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(msg) = msg {
+            assert_eq!(
+                "TempHum id=0x2F01 21.3°C 45% batt=ok rssi=6",
+                msg.to_string()
+            );
+        } else {
+            panic!("Expected protocol message, got {:?}", msg);
+        }
+
+        let msg = parse_message(&[0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(msg) = msg {
+            assert_eq!("Lighting1 Arc B5 On rssi=7", msg.to_string());
+        } else {
+            panic!("Expected protocol message, got {:?}", msg);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Messages as Domoticz device updates, for applications replacing the RFXCOM
+//! hardware of Domoticz with a gateway written with this library.
+//!
+//! The updates are in the JSON format of the `domoticz/in` MQTT topic, with the
+//! `nvalue` and `svalue` of the Domoticz device type the RFXCOM hardware creates
+//! for the message, and the battery level and signal level:
+//!
+//! ```json
+//! {"Battery":100,"RSSI":6,"idx":7,"nvalue":0,"svalue":"21.3;45;1"}
+//! ```
+//!
+//! The Domoticz device `idx` of each `DeviceId` is up to the application.
+use crate::trx_command::{
+    BatteryStatus, CurtainCommand, HomeConfortCommand, Lighting1Command, Lighting2Command,
+    ProtocolMessage,
+};
+use serde_json::json;
+
+/// Compass points of the Domoticz wind direction
+const WIND_DIRECTIONS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// nvalue of switches turned on
+const SWITCH_ON: u8 = 1;
+/// nvalue of switches turned off
+const SWITCH_OFF: u8 = 0;
+/// nvalue of dimmers set to the level in svalue
+const SWITCH_SET_LEVEL: u8 = 2;
+/// Battery level Domoticz shows as unknown
+const BATTERY_UNKNOWN: u8 = 255;
+
+impl ProtocolMessage {
+    /// Formats the message as an update of the Domoticz device `idx`, see
+    /// `rfxtrx433::domoticz`. Returns None for messages without a matching
+    /// Domoticz device, or commands that don't change its state.
+    pub fn to_domoticz_json(&self, idx: u64) -> Option<String> {
+        let (nvalue, svalue, battery) = match self {
+            ProtocolMessage::Lighting1(msg) => match msg.command {
+                Lighting1Command::On | Lighting1Command::AllOn => (SWITCH_ON, String::new(), None),
+                Lighting1Command::Off | Lighting1Command::AllOff => {
+                    (SWITCH_OFF, String::new(), None)
+                }
+                _ => return None,
+            },
+            ProtocolMessage::Lighting2(msg) => match msg.command {
+                Lighting2Command::On | Lighting2Command::GroupOn => {
+                    (SWITCH_ON, String::new(), None)
+                }
+                Lighting2Command::Off | Lighting2Command::GroupOff => {
+                    (SWITCH_OFF, String::new(), None)
+                }
+                // Levels are 0 - 15, Domoticz uses percent
+                Lighting2Command::SetLevel | Lighting2Command::SetGroupLevel => (
+                    SWITCH_SET_LEVEL,
+                    (msg.level.min(15) as u32 * 100 / 15).to_string(),
+                    None,
+                ),
+            },
+            ProtocolMessage::HomeConfort(msg) => match msg.command {
+                HomeConfortCommand::On | HomeConfortCommand::GroupOn => {
+                    (SWITCH_ON, String::new(), None)
+                }
+                HomeConfortCommand::Off | HomeConfortCommand::GroupOff => {
+                    (SWITCH_OFF, String::new(), None)
+                }
+            },
+            // Blinds are 0 when open and 1 when closed
+            ProtocolMessage::Curtain(msg) => match msg.command {
+                CurtainCommand::Open => (0, String::new(), None),
+                CurtainCommand::Close => (1, String::new(), None),
+                _ => return None,
+            },
+            // The security device states are the RFXCOM status codes
+            ProtocolMessage::Security1(msg) => (msg.status as u8, String::new(), Some(msg.battery)),
+            ProtocolMessage::Temp(msg) => (0, format!("{:.1}", msg.temp), Some(msg.battery)),
+            ProtocolMessage::Hum(msg) => (
+                msg.humidity,
+                u8::from(msg.humidity_status).to_string(),
+                Some(msg.battery),
+            ),
+            ProtocolMessage::TempHum(msg) => (
+                0,
+                format!(
+                    "{:.1};{};{}",
+                    msg.temp,
+                    msg.humidity,
+                    u8::from(msg.humidity_status)
+                ),
+                Some(msg.battery),
+            ),
+            ProtocolMessage::TempHumBaro(msg) => (
+                0,
+                format!(
+                    "{:.1};{};{};{};{}",
+                    msg.temp,
+                    msg.humidity,
+                    u8::from(msg.humidity_status),
+                    msg.baro,
+                    u8::from(msg.forecast)
+                ),
+                Some(msg.battery),
+            ),
+            // kWh meter, the power in W and the energy in Wh
+            ProtocolMessage::Energy(msg) => (
+                0,
+                format!("{};{:.0}", msg.instant_power, msg.total_usage * 1000.0),
+                Some(msg.battery),
+            ),
+            ProtocolMessage::Power(msg) => (
+                0,
+                format!("{:.1};{:.0}", msg.power, msg.energy * 1000.0),
+                None,
+            ),
+            ProtocolMessage::Weight(msg) => (0, format!("{:.1}", msg.weight), None),
+            // Wind, speeds in 0.1 m/s
+            ProtocolMessage::WeatherStation(msg) => (
+                0,
+                format!(
+                    "{};{};{:.0};{:.0};{:.1};{:.1}",
+                    msg.wind_direction,
+                    wind_direction_name(msg.wind_direction),
+                    msg.wind_speed * 10.0,
+                    msg.wind_gust * 10.0,
+                    msg.temp,
+                    msg.chill
+                ),
+                Some(msg.battery),
+            ),
+            _ => return None,
+        };
+        let battery = battery.map_or(BATTERY_UNKNOWN, BatteryStatus::percentage);
+        let rssi = self.rssi()?.value();
+        let update = json!({
+            "idx": idx,
+            "nvalue": nvalue,
+            "svalue": svalue,
+            "Battery": battery,
+            "RSSI": rssi,
+        });
+        Some(update.to_string())
+    }
+}
+
+/// Compass point of a wind direction in degrees
+fn wind_direction_name(degrees: u16) -> &'static str {
+    // In 0.01 degrees, 22.5 degrees per point centered on the point
+    WIND_DIRECTIONS[((degrees as u32 * 100 + 1125) / 2250 % 16) as usize]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::parse_frame;
+
+    #[test]
+    fn domoticz_updates() {
+        let frame = [
+            0x0A, 0x52, 0x01, 0x00, 0x2F, 0x01, 0x00, 0xD5, 0x2D, 0x01, 0x69,
+        ];
+        let msg = parse_frame(&frame).unwrap().message;
+        assert_eq!(
+            Some(r#"{"Battery":100,"RSSI":6,"idx":7,"nvalue":0,"svalue":"21.3;45;1"}"#),
+            msg.to_domoticz_json(7).as_deref()
+        );
+
+        // Lighting1 B5 on
+        let frame = [0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70];
+        let msg = parse_frame(&frame).unwrap().message;
+        assert_eq!(
+            Some(r#"{"Battery":255,"RSSI":7,"idx":3,"nvalue":1,"svalue":""}"#),
+            msg.to_domoticz_json(3).as_deref()
+        );
+
+        assert_eq!("N", wind_direction_name(0));
+        assert_eq!("NNE", wind_direction_name(23));
+        assert_eq!("SW", wind_direction_name(225));
+        assert_eq!("N", wind_direction_name(350));
+    }
+}
@@ -0,0 +1,191 @@
+//! Limiting of the transmit time to the duty cycle allowed in the band
+use crate::{Result, TRXError};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Share of the time the device may transmit, see `RFXtrx433Builder::duty_cycle`.
+///
+/// The limits depend on the band and country, in Europe most 433 MHz devices may
+/// transmit 10% of the time and 868 MHz devices 1% of an hour.
+///
+/// Packets sent with `Sender::send_raw` aren't counted.
+#[derive(Clone, Copy, Debug)]
+pub struct DutyCycleOptions {
+    /// Allowed transmit time in percent of `period`, more than 0 and at most 100
+    pub percent: f32,
+    /// Period the transmit time is counted over
+    pub period: Duration,
+    /// Estimated transmit time of a command, including the repeats the device sends
+    pub airtime: Duration,
+    /// Return `TRXError::DutyCycleExceeded` instead of waiting until the command
+    /// can be sent
+    pub reject: bool,
+}
+
+impl Default for DutyCycleOptions {
+    fn default() -> Self {
+        DutyCycleOptions {
+            percent: 10.0,
+            period: Duration::from_secs(3600),
+            airtime: Duration::from_millis(500),
+            reject: false,
+        }
+    }
+}
+
+impl DutyCycleOptions {
+    /// Returns `TRXError::InvalidArgument` unless the percentage is in (0, 100]
+    /// and the period isn't zero
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(self.percent > 0.0 && self.percent <= 100.0) {
+            return Err(TRXError::InvalidArgument(format!(
+                "Duty cycle {}% not in (0, 100]",
+                self.percent
+            )));
+        }
+        if self.period.is_zero() {
+            return Err(TRXError::InvalidArgument(
+                "The duty cycle period must not be zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Transmit times within the last period
+#[derive(Debug)]
+pub(crate) struct DutyCycleLimiter {
+    options: DutyCycleOptions,
+    budget: Duration,
+    // Start and length of the transmissions, oldest first
+    sent: VecDeque<(Instant, Duration)>,
+}
+
+impl DutyCycleLimiter {
+    /// `options` must be valid, see `DutyCycleOptions::validate`
+    pub(crate) fn new(options: DutyCycleOptions) -> Self {
+        DutyCycleLimiter {
+            options,
+            budget: options.period.mul_f32(options.percent / 100.0),
+            sent: VecDeque::new(),
+        }
+    }
+
+    /// Estimated transmit time of a command
+    pub(crate) fn airtime(&self) -> Duration {
+        self.options.airtime
+    }
+
+    /// Returns how long to wait before transmitting for `airtime` at `now`, or an
+    /// error if the limiter rejects commands or the airtime exceeds the budget.
+    /// A zero wait reserves the airtime.
+    pub(crate) fn reserve(&mut self, airtime: Duration, now: Instant) -> Result<Duration> {
+        if airtime > self.budget {
+            return Err(TRXError::InvalidArgument(format!(
+                "Transmit time {:?} exceeds the duty cycle of {:?}",
+                airtime, self.budget
+            )));
+        }
+        let period = self.options.period;
+        while let Some((start, _)) = self.sent.front() {
+            if now.duration_since(*start) < period {
+                break;
+            }
+            self.sent.pop_front();
+        }
+
+        // Wait for the oldest transmissions to leave the period until there's room
+        let mut used: Duration = self.sent.iter().map(|(_, len)| *len).sum();
+        let mut wait = Duration::ZERO;
+        for (start, len) in &self.sent {
+            if used + airtime <= self.budget {
+                break;
+            }
+            used -= *len;
+            wait = (*start + period).saturating_duration_since(now);
+        }
+        if wait.is_zero() {
+            self.sent.push_back((now, airtime));
+        } else if self.options.reject {
+            return Err(TRXError::DutyCycleExceeded(wait));
+        }
+        Ok(wait)
+    }
+
+    /// Transmit time left in the current period
+    pub(crate) fn remaining(&self, now: Instant) -> Duration {
+        let used: Duration = self
+            .sent
+            .iter()
+            .filter(|(start, _)| now.duration_since(*start) < self.options.period)
+            .map(|(_, len)| *len)
+            .sum();
+        self.budget.saturating_sub(used)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn duty_cycle() {
+        let mut limiter = DutyCycleLimiter::new(DutyCycleOptions {
+            percent: 1.0,
+            period: Duration::from_secs(100),
+            airtime: Duration::from_millis(400),
+            reject: false,
+        });
+        let airtime = limiter.airtime();
+        let start = Instant::now();
+        assert_eq!(Duration::ZERO, limiter.reserve(airtime, start).unwrap());
+        let later = start + Duration::from_secs(10);
+        assert_eq!(Duration::ZERO, limiter.reserve(airtime, later).unwrap());
+        assert_eq!(Duration::from_millis(200), limiter.remaining(later));
+
+        // Waits for the first transmission to leave the period
+        assert_eq!(
+            Duration::from_secs(90),
+            limiter.reserve(airtime, later).unwrap()
+        );
+        assert_eq!(
+            Duration::ZERO,
+            limiter
+                .reserve(airtime, start + Duration::from_secs(100))
+                .unwrap()
+        );
+        assert!(limiter.reserve(Duration::from_secs(2), later).is_err());
+
+        let mut limiter = DutyCycleLimiter::new(DutyCycleOptions {
+            reject: true,
+            ..Default::default()
+        });
+        limiter.reserve(Duration::from_secs(360), start).unwrap();
+        assert!(matches!(
+            limiter.reserve(Duration::from_secs(1), start),
+            Err(TRXError::DutyCycleExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert!(DutyCycleOptions::default().validate().is_ok());
+        for percent in [0.0, -1.0, 100.5, f32::NAN, f32::INFINITY] {
+            let options = DutyCycleOptions {
+                percent,
+                ..Default::default()
+            };
+            assert!(matches!(
+                options.validate(),
+                Err(TRXError::InvalidArgument(_))
+            ));
+        }
+        let options = DutyCycleOptions {
+            period: Duration::ZERO,
+            ..Default::default()
+        };
+        assert!(options.validate().is_err());
+    }
+}
@@ -6,6 +6,12 @@ pub enum TRXError {
     /// Couldn't find a device with the given serial number
     #[error("No device with serial number {0} found")]
     DeviceWithSerialNotFound(String),
+    /// Couldn't find a device plugged in at the given USB location
+    #[error("No device at USB location {0} found")]
+    DeviceAtLocationNotFound(String),
+    /// No serial port with a RFXtrx device answering was found
+    #[error("No RFXtrx device found")]
+    NoDeviceFound,
     /// Sent when the reader is shut down
     #[error("System was shutdown during operation")]
     Shutdown,
@@ -28,6 +34,24 @@ pub enum TRXError {
         /// Unknown subtype
         sub_type: u8,
     },
+    /// Unknown command in a protocol message
+    #[error("Unknown command {command} for packet type {packet_type:?}")]
+    UnknownCommand {
+        /// Packet type
+        packet_type: crate::trx_command::PacketType,
+        /// Unknown command
+        command: u8,
+    },
+    /// Invalid value of a field in a protocol message
+    #[error("Invalid {field} {value} for packet type {packet_type:?}")]
+    InvalidValue {
+        /// Packet type
+        packet_type: crate::trx_command::PacketType,
+        /// Name of the field
+        field: &'static str,
+        /// Invalid value
+        value: u8,
+    },
     /// Unknown command in an interface message
     #[error("Unknown interface message command: {0}")]
     UnknownInterfaceMessageCommand(u8),
@@ -43,6 +67,38 @@ pub enum TRXError {
     /// IO error
     #[error("IO error")]
     IO(#[from] std::io::Error),
+    /// JSON error
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The device refused to transmit
+    #[error("Transmit failed: {0:?}")]
+    TransmitFailed(crate::trx_command::TransmitError),
+    /// The receiver started message didn't contain the RFXCOM copyright
+    #[error("Invalid copyright message: {0}")]
+    InvalidCopyright(String),
+    /// The serial port task panicked or was cancelled
+    #[error("Serial port task failed: {0}")]
+    TaskFailed(String),
+    /// No message was received in time
+    #[error("Timeout")]
+    Timeout,
+    /// The device reported an error
+    #[error("Device error code {0}")]
+    ExtError(u8),
+    /// The device didn't recognize the command sent to it
+    #[error("Device rejected command {0}")]
+    WrongCommand(u8),
+    /// Transmit to a RFY remote that isn't stored in the device
+    #[error("Unknown RFY remote")]
+    UnknownRfyRemote,
+    /// Transmitting now would exceed the duty cycle limit, it's possible again
+    /// after the duration
+    #[error("Duty cycle limit reached, retry in {0:?}")]
+    DutyCycleExceeded(std::time::Duration),
+    /// Invalid argument passed to a command
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
     /// Channel error
     #[error("Tokio send error: {0}")]
     TokioSendError(String),
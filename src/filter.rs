@@ -0,0 +1,146 @@
+//! Subscriptions to the messages of some packet types or devices
+use crate::{DeviceId, PacketType, ReceivedMessage};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast;
+
+/// Selects messages by packet type, sub type and device, see `Receiver::subscribe_filtered`.
+///
+/// A message is selected if its packet type, its packet type and sub type or its
+/// device is added to the filter. An empty filter selects all messages.
+///
+/// ```
+/// use rfxtrx433::{Filter, PacketType};
+///
+/// // Temperature sensors, and AC switches but not the other Lighting2 sub types
+/// let filter = Filter::new()
+///     .packet_type(PacketType::TEMP)
+///     .packet_type(PacketType::TempHum)
+///     .sub_type(PacketType::Lighting2, 0x00);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filter {
+    packet_types: HashSet<PacketType>,
+    sub_types: HashSet<(PacketType, u8)>,
+    devices: HashSet<DeviceId>,
+}
+
+impl Filter {
+    /// Creates an empty filter, selecting all messages
+    pub fn new() -> Self {
+        Filter::default()
+    }
+
+    /// Selects the messages of `packet_type`
+    pub fn packet_type(mut self, packet_type: PacketType) -> Self {
+        self.packet_types.insert(packet_type);
+        self
+    }
+
+    /// Selects the messages of `packet_type` with `sub_type`
+    pub fn sub_type(mut self, packet_type: PacketType, sub_type: u8) -> Self {
+        self.sub_types.insert((packet_type, sub_type));
+        self
+    }
+
+    /// Selects the messages from the device with `id`
+    pub fn device(mut self, id: DeviceId) -> Self {
+        self.devices.insert(id);
+        self
+    }
+
+    /// Returns true if `msg` is selected
+    pub fn matches(&self, msg: &ReceivedMessage) -> bool {
+        if self.packet_types.is_empty() && self.sub_types.is_empty() && self.devices.is_empty() {
+            return true;
+        }
+        let packet_type = msg.packet_type();
+        self.packet_types.contains(&packet_type)
+            || self.sub_types.contains(&(packet_type, msg.sub_type()))
+            || msg.device_id().is_some_and(|id| self.devices.contains(&id))
+    }
+}
+
+/// A filtered subscriber's filter and channel
+type Subscriber = (Filter, broadcast::Sender<ReceivedMessage>);
+
+/// The filtered subscribers, shared by the receiver and the serial port task
+#[derive(Clone, Debug)]
+pub(crate) struct FilteredSubscribers {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+    capacity: usize,
+}
+
+impl FilteredSubscribers {
+    /// Subscribers get channels of `capacity` messages
+    pub(crate) fn new(capacity: usize) -> Self {
+        FilteredSubscribers {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            capacity,
+        }
+    }
+
+    pub(crate) fn subscribe(&self, filter: Filter) -> broadcast::Receiver<ReceivedMessage> {
+        let (tx, rx) = broadcast::channel(self.capacity);
+        self.lock().push((filter, tx));
+        rx
+    }
+
+    /// Sends `msg` to the subscribers selecting it, and forgets the dropped subscribers
+    pub(crate) fn send(&self, msg: &ReceivedMessage) {
+        self.lock().retain(|(filter, tx)| {
+            if tx.receiver_count() == 0 {
+                return false;
+            }
+            if filter.matches(msg) {
+                let _ = tx.send(msg.clone());
+            }
+            true
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Subscriber>> {
+        // A panic while sending leaves the subscribers consistent, keep using them
+        self.subscribers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::parse_frame;
+
+    #[test]
+    fn filter() {
+        let lighting1 = parse_frame(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70]).unwrap();
+        let temp_hum = parse_frame(&[
+            0x0A, 0x52, 0x01, 0x00, 0x2F, 0x01, 0x00, 0xD5, 0x2D, 0x01, 0x69,
+        ])
+        .unwrap();
+        let id = lighting1.device_id().unwrap();
+
+        assert!(Filter::new().matches(&lighting1));
+        let filter = Filter::new().packet_type(PacketType::TempHum);
+        assert!(filter.matches(&temp_hum));
+        assert!(!filter.matches(&lighting1));
+        assert!(Filter::new().device(id).matches(&lighting1));
+        assert!(!Filter::new().device(id).matches(&temp_hum));
+        assert!(Filter::new()
+            .sub_type(PacketType::Lighting1, 0x01)
+            .matches(&lighting1));
+        assert!(!Filter::new()
+            .sub_type(PacketType::Lighting1, 0x00)
+            .matches(&lighting1));
+
+        let subscribers = FilteredSubscribers::new(4);
+        let mut rx = subscribers.subscribe(filter);
+        drop(subscribers.subscribe(Filter::new()));
+        subscribers.send(&lighting1);
+        subscribers.send(&temp_hum);
+        assert_eq!(1, subscribers.lock().len());
+        assert_eq!(PacketType::TempHum, rx.try_recv().unwrap().packet_type());
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! Parsing and building frames without a device, e.g. for log analyzers, tests and
+//! bridges. Frames start with the length byte, as sent over the serial port and
+//! recorded with `RFXtrx433Builder::record`.
+//!
+//! The builders take the sequence number to put in the frame and the same arguments
+//! as the `Sender` methods, returning the frame to write.
+use crate::{
+    trx_command::{
+        self, AsyncPort, CameraCommand, ChimeSubType, Frequency, HomeConfortCommand,
+        HunterFanCommand, Lighting1Command, Lighting1SubType, Lighting2Command, Lighting2SubType,
+        Lighting3Command, Lighting5SubType, Lighting6Command, Lighting6SubType, ReceivedCommand,
+        RemoteSubType, RfyCommand, RfySubType, Security1Status, Security1SubType, Thermostat1Mode,
+        Thermostat1Status, Thermostat1SubType, Thermostat3Command, Thermostat3SubType,
+    },
+    HouseCode, Protocols1, Protocols2, Protocols3, Protocols4, Protocols868_1, Protocols868_2,
+    Protocols868_3, Protocols868_4, ReceivedMessage, RemoteId, Result, TRXError, TemperatureUnit,
+    UnitCode,
+};
+use std::time::{Instant, SystemTime};
+
+/// Parses a received frame. Only protocol messages are returned, interface
+/// messages give `TRXError::UnexpectedMessage`. The temperature unit is Celsius and
+/// the timestamps are set to now.
+pub fn parse_frame(frame: &[u8]) -> Result<ReceivedMessage> {
+    let len = match frame.first() {
+        Some(len) => *len as usize,
+        None => {
+            return Err(TRXError::NotEnoughData {
+                received: 0,
+                expected: 1,
+            })
+        }
+    };
+    if frame.len() <= len {
+        return Err(TRXError::NotEnoughData {
+            received: frame.len() - 1,
+            expected: len,
+        });
+    }
+    match trx_command::parse_packet(&frame[1..=len])? {
+        (header, ReceivedCommand::ProtocolMessage(message)) => Ok(ReceivedMessage {
+            message,
+            header,
+            raw: frame[..=len].to_vec(),
+            timestamp: SystemTime::now(),
+            instant: Instant::now(),
+            temperature_unit: TemperatureUnit::Celsius,
+        }),
+        (_, ReceivedCommand::InterfaceMessage(msg)) => {
+            Err(TRXError::UnexpectedMessage(format!("{:?}", msg)))
+        }
+    }
+}
+
+/// Resets the device
+pub fn reset(seqnbr: u8) -> Vec<u8> {
+    trx_command::reset(seqnbr)
+}
+
+/// Requests the device status
+pub fn get_status(seqnbr: u8) -> Vec<u8> {
+    trx_command::get_status(seqnbr)
+}
+
+/// Starts the receiver, needed after a reset
+pub fn start_receiver(seqnbr: u8) -> Vec<u8> {
+    trx_command::start_receiver(seqnbr)
+}
+
+/// Enables all receive protocols
+pub fn enable_all(seqnbr: u8) -> Vec<u8> {
+    trx_command::enable_all(seqnbr)
+}
+
+/// Saves the mode to the device's flash memory
+pub fn save(seqnbr: u8) -> Vec<u8> {
+    trx_command::save(seqnbr)
+}
+
+/// Sets the 433 MHz receive protocols, with the default transmit power
+pub fn set_mode(
+    seqnbr: u8,
+    frequency: Frequency,
+    protos_1: Protocols1,
+    protos_2: Protocols2,
+    protos_3: Protocols3,
+    protos_4: Protocols4,
+) -> Vec<u8> {
+    trx_command::set_mode(seqnbr, frequency, 0, protos_1, protos_2, protos_3, protos_4)
+}
+
+/// Sets the 868 MHz receive protocols, with the default transmit power
+pub fn set_mode_868(
+    seqnbr: u8,
+    protos_1: Protocols868_1,
+    protos_2: Protocols868_2,
+    protos_3: Protocols868_3,
+    protos_4: Protocols868_4,
+) -> Vec<u8> {
+    trx_command::set_mode_868(seqnbr, 0, protos_1, protos_2, protos_3, protos_4)
+}
+
+/// A packet of any type, see `Sender::send_raw`
+pub fn raw_packet(seqnbr: u8, packet_type: u8, sub_type: u8, payload: &[u8]) -> Result<Vec<u8>> {
+    trx_command::raw_packet(seqnbr, packet_type, sub_type, payload)
+}
+
+/// Lighting1 command, see `Sender::send_lighting1`
+pub fn lighting1(
+    seqnbr: u8,
+    sub_type: Lighting1SubType,
+    house_code: HouseCode,
+    unit_code: UnitCode,
+    command: Lighting1Command,
+) -> Result<Vec<u8>> {
+    trx_command::lighting1(
+        seqnbr,
+        sub_type,
+        house_code.as_char(),
+        unit_code.value(),
+        command,
+    )
+}
+
+/// Lighting2 command, see `Sender::send_lighting2`
+pub fn lighting2(
+    seqnbr: u8,
+    sub_type: Lighting2SubType,
+    id: RemoteId,
+    unit_code: UnitCode,
+    command: Lighting2Command,
+    level: u8,
+) -> Result<Vec<u8>> {
+    trx_command::lighting2(
+        seqnbr,
+        sub_type,
+        id.value(),
+        unit_code.value(),
+        command,
+        level,
+    )
+}
+
+/// Lighting3 command, see `Sender::send_lighting3`
+pub fn lighting3(
+    seqnbr: u8,
+    system: RemoteId,
+    channels: u16,
+    command: Lighting3Command,
+) -> Result<Vec<u8>> {
+    trx_command::lighting3(seqnbr, system.value(), channels, command)
+}
+
+/// Lighting5 command, see `Sender::send_lighting5`
+pub fn lighting5(
+    seqnbr: u8,
+    sub_type: Lighting5SubType,
+    id: RemoteId,
+    unit_code: UnitCode,
+    command: u8,
+    level: u8,
+) -> Result<Vec<u8>> {
+    trx_command::lighting5(
+        seqnbr,
+        sub_type,
+        id.value(),
+        unit_code.value(),
+        command,
+        level,
+    )
+}
+
+/// Lighting6 command, see `Sender::send_lighting6`. The command sequence number,
+/// 0 - 4, and rolling code are counted per `id` by the sender.
+#[allow(clippy::too_many_arguments)]
+pub fn lighting6(
+    seqnbr: u8,
+    sub_type: Lighting6SubType,
+    id: RemoteId,
+    group_code: HouseCode,
+    unit_code: UnitCode,
+    command: Lighting6Command,
+    cmnd_seqnbr: u8,
+    rolling_code: u8,
+) -> Result<Vec<u8>> {
+    trx_command::lighting6(
+        seqnbr,
+        sub_type,
+        id.value(),
+        group_code.as_char(),
+        unit_code.value(),
+        command,
+        cmnd_seqnbr,
+        rolling_code,
+    )
+}
+
+/// Chime command, see `Sender::send_chime`
+pub fn chime(seqnbr: u8, sub_type: ChimeSubType, id: RemoteId, sound: u8) -> Result<Vec<u8>> {
+    trx_command::chime(seqnbr, sub_type, id.value(), sound)
+}
+
+/// RFY command, see `Sender::send_rfy`
+pub fn rfy(
+    seqnbr: u8,
+    sub_type: RfySubType,
+    id: RemoteId,
+    unit_code: UnitCode,
+    command: RfyCommand,
+) -> Result<Vec<u8>> {
+    trx_command::rfy(seqnbr, sub_type, id.value(), unit_code.value(), command)
+}
+
+/// HomeConfort command, see `Sender::send_home_confort`
+pub fn home_confort(
+    seqnbr: u8,
+    id: RemoteId,
+    house_code: HouseCode,
+    unit_code: UnitCode,
+    command: HomeConfortCommand,
+) -> Result<Vec<u8>> {
+    trx_command::home_confort(
+        seqnbr,
+        id.value(),
+        house_code.as_char(),
+        unit_code.value(),
+        command,
+    )
+}
+
+/// Hunter fan command, see `Sender::send_hunter_fan`
+pub fn hunter_fan(seqnbr: u8, id: [u8; 6], command: HunterFanCommand) -> Vec<u8> {
+    trx_command::hunter_fan(seqnbr, id, command)
+}
+
+/// Security1 command, see `Sender::send_security1`
+pub fn security1(
+    seqnbr: u8,
+    sub_type: Security1SubType,
+    id: RemoteId,
+    status: Security1Status,
+) -> Result<Vec<u8>> {
+    trx_command::security1(seqnbr, sub_type, id.value(), status)
+}
+
+/// Camera command, see `Sender::send_camera`
+pub fn camera(seqnbr: u8, house_code: HouseCode, command: CameraCommand) -> Result<Vec<u8>> {
+    trx_command::camera(seqnbr, house_code.as_char(), command)
+}
+
+/// Remote button press, see `Sender::send_remote`
+pub fn remote(
+    seqnbr: u8,
+    sub_type: RemoteSubType,
+    id: RemoteId,
+    command: u8,
+    toggle: bool,
+) -> Result<Vec<u8>> {
+    trx_command::remote(seqnbr, sub_type, id.value(), command, toggle)
+}
+
+/// Thermostat1 message, see `Sender::send_thermostat1`
+pub fn thermostat1(
+    seqnbr: u8,
+    sub_type: Thermostat1SubType,
+    id: RemoteId,
+    temperature: u8,
+    set_point: u8,
+    status: Thermostat1Status,
+    mode: Thermostat1Mode,
+) -> Result<Vec<u8>> {
+    trx_command::thermostat1(
+        seqnbr,
+        sub_type,
+        id.value(),
+        temperature,
+        set_point,
+        status,
+        mode,
+    )
+}
+
+/// Thermostat3 command, see `Sender::send_thermostat3`
+pub fn thermostat3(
+    seqnbr: u8,
+    sub_type: Thermostat3SubType,
+    unit_id: RemoteId,
+    command: Thermostat3Command,
+) -> Result<Vec<u8>> {
+    trx_command::thermostat3(seqnbr, sub_type, unit_id.value(), command)
+}
+
+/// Raw pulse train frames, see `Sender::send_raw_pulses`. The frames are numbered
+/// from `seqnbr` like `Sender` numbers commands, skipping 0xFF, which is reserved
+/// for internal commands, and must be sent in order.
+pub fn raw_pulses(seqnbr: u8, pulses: &[u16], repeat: u8) -> Result<Vec<Vec<u8>>> {
+    trx_command::raw_pulses(seqnbr, pulses, repeat)
+}
+
+/// Async port configuration, see `Sender::configure_async_port`
+pub fn async_port(seqnbr: u8, config: &AsyncPort) -> Result<Vec<u8>> {
+    trx_command::async_port(seqnbr, config)
+}
+
+/// Async data frames, see `Sender::send_async_data`. The frames are numbered
+/// from `seqnbr` like `Sender` numbers commands, skipping 0xFF, which is reserved
+/// for internal commands.
+pub fn async_data(seqnbr: u8, data: &[u8]) -> Result<Vec<Vec<u8>>> {
+    trx_command::async_data(seqnbr, data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PacketType, ProtocolMessage};
+
+    #[test]
+    fn round_trip() {
+        let house_code = HouseCode::new('B').unwrap();
+        let unit_code = UnitCode::new(5).unwrap();
+        let frame = lighting1(
+            3,
+            Lighting1SubType::Arc,
+            house_code,
+            unit_code,
+            Lighting1Command::On,
+        )
+        .unwrap();
+        let msg = parse_frame(&frame).unwrap();
+        assert_eq!(PacketType::Lighting1, msg.packet_type());
+        assert_eq!(3, msg.seqnbr());
+        assert_eq!(frame, msg.raw);
+        if let ProtocolMessage::Lighting1(msg) = msg.message {
+            assert_eq!(house_code, msg.house_code);
+            assert_eq!(unit_code, msg.unit_code);
+            assert_eq!(Lighting1Command::On, msg.command);
+        } else {
+            panic!("Expected Lighting1, got {:?}", msg.message);
+        }
+
+        assert!(parse_frame(&[]).is_err());
+        assert!(parse_frame(&[0x07, 0x10, 0x01]).is_err());
+        // Transmit acknowledgement
+        assert!(matches!(
+            parse_frame(&[0x04, 0x02, 0x01, 0x03, 0x00]),
+            Err(TRXError::UnexpectedMessage(_))
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+//! Home Assistant MQTT discovery, so the devices heard appear in Home Assistant
+//! without configuration.
+//!
+//! The discovery messages describe the entities of a device and should be published
+//! retained. The entities expect the device's messages on its state topic, in the
+//! representation of `rfxtrx433::json`, and switches and covers are controlled by
+//! publishing the command names, e.g. "On" or "Close", to the command topic. Both
+//! topics are under `DiscoveryOptions::state_prefix`, see `state_topic`.
+use crate::{trx_command::ProtocolMessage, DeviceId, DeviceRecord, DeviceRegistry};
+use serde_json::{json, Value};
+
+/// Topics of the discovery messages
+#[derive(Clone, Debug)]
+pub struct DiscoveryOptions {
+    /// Prefix Home Assistant subscribes to, the default is "homeassistant"
+    pub discovery_prefix: String,
+    /// Prefix of the state and command topics, the default is "rfxtrx433"
+    pub state_prefix: String,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        DiscoveryOptions {
+            discovery_prefix: "homeassistant".to_string(),
+            state_prefix: "rfxtrx433".to_string(),
+        }
+    }
+}
+
+/// A discovery config to publish, retained, on `topic`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveryMessage {
+    /// Topic to publish on, e.g. "homeassistant/sensor/rfxtrx433_52_01_2f01/temp/config"
+    pub topic: String,
+    /// The JSON config of the entity
+    pub payload: String,
+}
+
+/// Topic the messages of the device with `id` are expected on,
+/// e.g. "rfxtrx433/52_01_2f01". Commands are received on the topic + "/set".
+pub fn state_topic(options: &DiscoveryOptions, id: DeviceId) -> String {
+    format!("{}/{}", options.state_prefix, node_id(id))
+}
+
+/// Node id of the device in the discovery topics
+fn node_id(id: DeviceId) -> String {
+    format!(
+        "{:02x}_{:02x}_{:x}",
+        id.packet_type(),
+        id.sub_type(),
+        id.address()
+    )
+}
+
+/// A sensor entity reading `key` of the messages
+struct Sensor {
+    key: &'static str,
+    name: &'static str,
+    device_class: Option<&'static str>,
+    unit: Option<&'static str>,
+    state_class: &'static str,
+}
+
+const fn sensor(
+    key: &'static str,
+    name: &'static str,
+    device_class: Option<&'static str>,
+    unit: Option<&'static str>,
+) -> Sensor {
+    Sensor {
+        key,
+        name,
+        device_class,
+        unit,
+        state_class: "measurement",
+    }
+}
+
+const TEMP: Sensor = sensor("temp", "Temperature", Some("temperature"), Some("°C"));
+const HUMIDITY: Sensor = sensor("humidity", "Humidity", Some("humidity"), Some("%"));
+const BARO: Sensor = sensor(
+    "baro",
+    "Pressure",
+    Some("atmospheric_pressure"),
+    Some("hPa"),
+);
+const RAIN_TOTAL: Sensor = Sensor {
+    state_class: "total_increasing",
+    ..sensor("rain_total", "Rain", Some("precipitation"), Some("mm"))
+};
+
+/// Returns the discovery messages for the entities of `device`, none if
+/// its messages aren't supported
+pub fn discovery_messages(
+    device: &DeviceRecord,
+    options: &DiscoveryOptions,
+) -> Vec<DiscoveryMessage> {
+    let sensors = match &device.last_message {
+        ProtocolMessage::Temp(_) => vec![TEMP],
+        ProtocolMessage::Hum(_) => vec![HUMIDITY],
+        ProtocolMessage::TempHum(_) => vec![TEMP, HUMIDITY],
+        ProtocolMessage::TempHumBaro(_) => vec![TEMP, HUMIDITY, BARO],
+        ProtocolMessage::TempRain(_) => vec![TEMP, RAIN_TOTAL],
+        ProtocolMessage::Bbq(_) => vec![
+            sensor(
+                "food_temp",
+                "Food temperature",
+                Some("temperature"),
+                Some("°C"),
+            ),
+            sensor(
+                "bbq_temp",
+                "BBQ temperature",
+                Some("temperature"),
+                Some("°C"),
+            ),
+        ],
+        ProtocolMessage::Energy(_) => vec![
+            sensor("instant_power", "Power", Some("power"), Some("W")),
+            Sensor {
+                state_class: "total_increasing",
+                ..sensor("total_usage", "Energy", Some("energy"), Some("kWh"))
+            },
+        ],
+        ProtocolMessage::Power(_) => vec![
+            sensor("voltage", "Voltage", Some("voltage"), Some("V")),
+            sensor("current", "Current", Some("current"), Some("A")),
+            sensor("power", "Power", Some("power"), Some("W")),
+            Sensor {
+                state_class: "total_increasing",
+                ..sensor("energy", "Energy", Some("energy"), Some("kWh"))
+            },
+        ],
+        ProtocolMessage::Weight(_) => vec![sensor("weight", "Weight", Some("weight"), Some("kg"))],
+        ProtocolMessage::WeatherStation(_) => vec![
+            TEMP,
+            HUMIDITY,
+            BARO,
+            RAIN_TOTAL,
+            sensor("wind_speed", "Wind speed", Some("wind_speed"), Some("m/s")),
+            sensor("wind_gust", "Wind gust", Some("wind_speed"), Some("m/s")),
+            sensor("wind_direction", "Wind direction", None, Some("°")),
+            sensor(
+                "rain_rate",
+                "Rain rate",
+                Some("precipitation_intensity"),
+                Some("mm/h"),
+            ),
+            sensor("uv", "UV index", None, None),
+            sensor("solar", "Solar radiation", Some("irradiance"), Some("W/m²")),
+        ],
+        _ => Vec::new(),
+    };
+
+    let mut messages = Vec::new();
+    for sensor in &sensors {
+        let template = format!("{{{{ value_json.{} }}}}", sensor.key);
+        let mut config = json!({
+            "name": sensor.name,
+            "value_template": template,
+            "state_class": sensor.state_class,
+        });
+        set(&mut config, "device_class", sensor.device_class);
+        set(&mut config, "unit_of_measurement", sensor.unit);
+        messages.push(message(device, options, "sensor", sensor.key, config));
+    }
+
+    match &device.last_message {
+        ProtocolMessage::Lighting1(_)
+        | ProtocolMessage::Lighting2(_)
+        | ProtocolMessage::HomeConfort(_) => {
+            let config = json!({
+                "name": null,
+                "value_template": "{{ value_json.command }}",
+                "state_on": "On",
+                "state_off": "Off",
+                "payload_on": "On",
+                "payload_off": "Off",
+            });
+            messages.push(message(device, options, "switch", "switch", config));
+        }
+        ProtocolMessage::Curtain(_) => {
+            let config = json!({
+                "name": null,
+                "payload_open": "Open",
+                "payload_close": "Close",
+                "payload_stop": "Stop",
+                "optimistic": true,
+            });
+            messages.push(message(device, options, "cover", "cover", config));
+        }
+        ProtocolMessage::Security1(_) => {
+            let config = json!({
+                "name": null,
+                "value_template": "{{ 'ON' if value_json.status in \
+                    ['Alarm', 'AlarmDelayed', 'Motion', 'Panic'] else 'OFF' }}",
+            });
+            messages.push(message(device, options, "binary_sensor", "alarm", config));
+        }
+        _ => {}
+    }
+
+    if !messages.is_empty() && device.last_message.rssi().is_some() {
+        let config = json!({
+            "name": "Signal level",
+            "value_template": "{{ value_json.rssi }}",
+            "state_class": "measurement",
+            "entity_category": "diagnostic",
+        });
+        messages.push(message(device, options, "sensor", "rssi", config));
+    }
+    messages
+}
+
+/// Adds the topics, unique id and device to `config`
+fn message(
+    device: &DeviceRecord,
+    options: &DiscoveryOptions,
+    component: &str,
+    object_id: &str,
+    mut config: Value,
+) -> DiscoveryMessage {
+    let node_id = node_id(device.id);
+    let state_topic = state_topic(options, device.id);
+    let unique_id = format!("{}_{}_{}", options.state_prefix, node_id, object_id);
+    let identifier = format!("{}_{}", options.state_prefix, node_id);
+    let name = format!("RFXtrx433 {}", device.id);
+    let device_info = json!({
+        "identifiers": [identifier],
+        "name": name,
+        "manufacturer": "RFXCOM",
+    });
+    if let Some(config) = config.as_object_mut() {
+        config.insert("unique_id".to_string(), unique_id.into());
+        config.insert("device".to_string(), device_info);
+        match component {
+            "switch" | "cover" => {
+                config.insert(
+                    "command_topic".to_string(),
+                    format!("{}/set", state_topic).into(),
+                );
+                if component == "switch" {
+                    config.insert("state_topic".to_string(), state_topic.into());
+                }
+            }
+            _ => {
+                config.insert("state_topic".to_string(), state_topic.into());
+            }
+        }
+    }
+    DiscoveryMessage {
+        topic: format!(
+            "{}/{}/{}_{}/{}/config",
+            options.discovery_prefix, component, options.state_prefix, node_id, object_id
+        ),
+        payload: config.to_string(),
+    }
+}
+
+/// Sets `key` in `config` if there is a `value`
+fn set(config: &mut Value, key: &str, value: Option<&str>) {
+    if let (Some(config), Some(value)) = (config.as_object_mut(), value) {
+        config.insert(key.to_string(), value.into());
+    }
+}
+
+impl DeviceRegistry {
+    /// Returns the Home Assistant discovery messages for all devices heard,
+    /// see `rfxtrx433::homeassistant`
+    pub fn discovery_messages(&self, options: &DiscoveryOptions) -> Vec<DiscoveryMessage> {
+        self.devices()
+            .iter()
+            .flat_map(|device| discovery_messages(device, options))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::parse_frame;
+
+    #[test]
+    fn temp_hum_discovery() {
+        let registry = DeviceRegistry::new();
+        let frame = [
+            0x0A, 0x52, 0x01, 0x00, 0x2F, 0x01, 0x00, 0xD5, 0x2D, 0x01, 0x69,
+        ];
+        registry.record(&parse_frame(&frame).unwrap());
+
+        let messages = registry.discovery_messages(&DiscoveryOptions::default());
+        assert_eq!(3, messages.len());
+        assert_eq!(
+            "homeassistant/sensor/rfxtrx433_52_01_2f01/temp/config",
+            messages[0].topic
+        );
+        let config: Value = serde_json::from_str(&messages[0].payload).unwrap();
+        assert_eq!("rfxtrx433/52_01_2f01", config["state_topic"]);
+        assert_eq!("{{ value_json.temp }}", config["value_template"]);
+        assert_eq!("°C", config["unit_of_measurement"]);
+        assert_eq!("rfxtrx433_52_01_2f01_temp", config["unique_id"]);
+        assert_eq!(
+            "homeassistant/sensor/rfxtrx433_52_01_2f01/rssi/config",
+            messages[2].topic
+        );
+    }
+}
@@ -0,0 +1,622 @@
+//! Device identifiers used in received messages and transmit commands
+use crate::trx_command::{
+    Bbq, Camera, Curtain, Energy, Fan, Funkbus, HomeConfort, Hum, HunterFan, Lighting1, Lighting2,
+    Lighting4, PacketType, Power, ProtocolMessage, RemoteControl, Security1, Security2, Temp,
+    TempHum, TempHumBaro, TempRain, WeatherStation, Weight,
+};
+use crate::{Result, TRXError};
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+/// House code of X10 style devices, 'A' - 'P'
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "char", into = "char")
+)]
+pub struct HouseCode(char);
+
+impl HouseCode {
+    /// Creates a house code, returns an error if it isn't 'A' - 'P'
+    pub fn new(house_code: char) -> Result<Self> {
+        if ('A'..='P').contains(&house_code) {
+            Ok(HouseCode(house_code))
+        } else {
+            Err(TRXError::InvalidArgument(format!(
+                "House code {} not in A - P",
+                house_code
+            )))
+        }
+    }
+
+    /// Returns the house code as a letter
+    pub fn as_char(self) -> char {
+        self.0
+    }
+}
+
+impl TryFrom<char> for HouseCode {
+    type Error = TRXError;
+
+    fn try_from(house_code: char) -> Result<Self> {
+        HouseCode::new(house_code)
+    }
+}
+
+impl TryFrom<u8> for HouseCode {
+    type Error = TRXError;
+
+    fn try_from(house_code: u8) -> Result<Self> {
+        HouseCode::new(house_code as char)
+    }
+}
+
+impl From<HouseCode> for char {
+    fn from(house_code: HouseCode) -> char {
+        house_code.0
+    }
+}
+
+impl From<HouseCode> for u8 {
+    fn from(house_code: HouseCode) -> u8 {
+        house_code.0 as u8
+    }
+}
+
+impl PartialEq<char> for HouseCode {
+    fn eq(&self, other: &char) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<HouseCode> for char {
+    fn eq(&self, other: &HouseCode) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Display for HouseCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for HouseCode {
+    type Err = TRXError;
+
+    /// Parses a single letter, in upper or lower case
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => HouseCode::new(c.to_ascii_uppercase()),
+            _ => Err(TRXError::InvalidArgument(format!(
+                "Invalid house code {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Unit code within a house code or remote, 1 - 16.
+/// Group commands are sent to unit code 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
+pub struct UnitCode(u8);
+
+impl UnitCode {
+    /// Creates a unit code, returns an error if it is above 16
+    pub fn new(unit_code: u8) -> Result<Self> {
+        if unit_code <= 16 {
+            Ok(UnitCode(unit_code))
+        } else {
+            Err(TRXError::InvalidArgument(format!(
+                "Unit code {} not in 0 - 16",
+                unit_code
+            )))
+        }
+    }
+
+    /// Returns the unit code
+    pub fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for UnitCode {
+    type Error = TRXError;
+
+    fn try_from(unit_code: u8) -> Result<Self> {
+        UnitCode::new(unit_code)
+    }
+}
+
+impl From<UnitCode> for u8 {
+    fn from(unit_code: UnitCode) -> u8 {
+        unit_code.0
+    }
+}
+
+impl PartialEq<u8> for UnitCode {
+    fn eq(&self, other: &u8) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<UnitCode> for u8 {
+    fn eq(&self, other: &UnitCode) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Display for UnitCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for UnitCode {
+    type Err = TRXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let unit_code = s
+            .trim()
+            .parse()
+            .map_err(|_| TRXError::InvalidArgument(format!("Invalid unit code {}", s)))?;
+        UnitCode::new(unit_code)
+    }
+}
+
+/// 16 bit id of a sensor, e.g. a thermometer. Most sensors pick a new id when
+/// the batteries are changed. Shown in hex, as by other RFXCOM software.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SensorId(u16);
+
+impl SensorId {
+    /// Returns the id
+    pub fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl From<u16> for SensorId {
+    fn from(id: u16) -> Self {
+        SensorId(id)
+    }
+}
+
+impl From<SensorId> for u16 {
+    fn from(id: SensorId) -> u16 {
+        id.0
+    }
+}
+
+impl PartialEq<u16> for SensorId {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<SensorId> for u16 {
+    fn eq(&self, other: &SensorId) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Display for SensorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04X}", self.0)
+    }
+}
+
+impl FromStr for SensorId {
+    type Err = TRXError;
+
+    /// Parses the id in hex, with or without a 0x prefix
+    fn from_str(s: &str) -> Result<Self> {
+        u16::from_str_radix(strip_hex_prefix(s), 16)
+            .map(SensorId)
+            .map_err(|_| TRXError::InvalidArgument(format!("Invalid sensor id {}", s)))
+    }
+}
+
+/// Id of a remote control or switch, up to 32 bits. How many bits are used
+/// depends on the protocol. Shown in hex, as by other RFXCOM software.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct RemoteId(u32);
+
+impl RemoteId {
+    /// Returns the id
+    pub fn value(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for RemoteId {
+    fn from(id: u32) -> Self {
+        RemoteId(id)
+    }
+}
+
+impl From<RemoteId> for u32 {
+    fn from(id: RemoteId) -> u32 {
+        id.0
+    }
+}
+
+impl PartialEq<u32> for RemoteId {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<RemoteId> for u32 {
+    fn eq(&self, other: &RemoteId) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Display for RemoteId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:06X}", self.0)
+    }
+}
+
+impl FromStr for RemoteId {
+    type Err = TRXError;
+
+    /// Parses the id in hex, with or without a 0x prefix
+    fn from_str(s: &str) -> Result<Self> {
+        u32::from_str_radix(strip_hex_prefix(s), 16)
+            .map(RemoteId)
+            .map_err(|_| TRXError::InvalidArgument(format!("Invalid remote id {}", s)))
+    }
+}
+
+fn strip_hex_prefix(s: &str) -> &str {
+    let s = s.trim();
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+/// Identifies a device across messages: the packet type, sub type and address.
+/// The address is the id, house code and unit code the protocol uses, so each
+/// unit of a remote or switch is a device of its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceId {
+    packet_type: u8,
+    sub_type: u8,
+    address: u64,
+}
+
+impl DeviceId {
+    /// Creates a device id
+    pub fn new(packet_type: PacketType, sub_type: u8, address: u64) -> Self {
+        DeviceId {
+            packet_type: packet_type as u8,
+            sub_type,
+            address,
+        }
+    }
+
+    /// Packet type byte
+    pub fn packet_type(self) -> u8 {
+        self.packet_type
+    }
+
+    /// Sub type byte
+    pub fn sub_type(self) -> u8 {
+        self.sub_type
+    }
+
+    /// Address, how it is composed depends on the packet type
+    pub fn address(self) -> u64 {
+        self.address
+    }
+}
+
+impl fmt::Display for DeviceId {
+    /// Formats as packet type, sub type and address in hex, e.g. "52/01/2F01"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02X}/{:02X}/{:04X}",
+            self.packet_type, self.sub_type, self.address
+        )
+    }
+}
+
+/// Decoded messages from a device, see `ProtocolMessage::device_id` for any message
+pub trait HasDeviceId {
+    /// Returns the id of the device that sent the message
+    fn device_id(&self) -> DeviceId;
+}
+
+impl ProtocolMessage {
+    /// Returns the id of the device that sent the message, or None for messages
+    /// that aren't from a device, e.g. undecoded or raw messages
+    pub fn device_id(&self) -> Option<DeviceId> {
+        match self {
+            ProtocolMessage::Lighting1(msg) => Some(msg.device_id()),
+            ProtocolMessage::Lighting2(msg) => Some(msg.device_id()),
+            ProtocolMessage::Lighting4(msg) => Some(msg.device_id()),
+            ProtocolMessage::Fan(msg) => Some(msg.device_id()),
+            ProtocolMessage::Curtain(msg) => Some(msg.device_id()),
+            ProtocolMessage::HomeConfort(msg) => Some(msg.device_id()),
+            ProtocolMessage::Funkbus(msg) => Some(msg.device_id()),
+            ProtocolMessage::HunterFan(msg) => Some(msg.device_id()),
+            ProtocolMessage::Security1(msg) => Some(msg.device_id()),
+            ProtocolMessage::Security2(msg) => Some(msg.device_id()),
+            ProtocolMessage::Camera(msg) => Some(msg.device_id()),
+            ProtocolMessage::RemoteControl(msg) => Some(msg.device_id()),
+            ProtocolMessage::Bbq(msg) => Some(msg.device_id()),
+            ProtocolMessage::TempRain(msg) => Some(msg.device_id()),
+            ProtocolMessage::Temp(msg) => Some(msg.device_id()),
+            ProtocolMessage::Hum(msg) => Some(msg.device_id()),
+            ProtocolMessage::TempHum(msg) => Some(msg.device_id()),
+            ProtocolMessage::TempHumBaro(msg) => Some(msg.device_id()),
+            ProtocolMessage::Energy(msg) => Some(msg.device_id()),
+            ProtocolMessage::Power(msg) => Some(msg.device_id()),
+            ProtocolMessage::Weight(msg) => Some(msg.device_id()),
+            ProtocolMessage::WeatherStation(msg) => Some(msg.device_id()),
+            ProtocolMessage::Undecoded(_)
+            | ProtocolMessage::AsyncPort(_)
+            | ProtocolMessage::AsyncData(_)
+            | ProtocolMessage::RawPulses(_)
+            | ProtocolMessage::NotParsed { .. } => None,
+        }
+    }
+}
+
+/// Address of a unit of a house code or remote
+fn unit_address(id: u32, unit_code: UnitCode) -> u64 {
+    (id as u64) << 8 | unit_code.value() as u64
+}
+
+impl HasDeviceId for Lighting1 {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Lighting1,
+            self.sub_type as u8,
+            unit_address(self.house_code.as_char() as u32, self.unit_code),
+        )
+    }
+}
+
+impl HasDeviceId for Lighting2 {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Lighting2,
+            self.sub_type as u8,
+            unit_address(self.id.value(), self.unit_code),
+        )
+    }
+}
+
+impl HasDeviceId for Lighting4 {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::Lighting4, self.sub_type, self.code as u64)
+    }
+}
+
+impl HasDeviceId for Fan {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::Fan, self.sub_type as u8, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for Curtain {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Curtain,
+            self.sub_type,
+            unit_address(self.house_code.as_char() as u32, self.unit_code),
+        )
+    }
+}
+
+impl HasDeviceId for HomeConfort {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::HomeConfort,
+            self.sub_type,
+            (self.id.value() as u64) << 16
+                | unit_address(self.house_code.as_char() as u32, self.unit_code),
+        )
+    }
+}
+
+impl HasDeviceId for Funkbus {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Funkbus,
+            self.sub_type as u8,
+            (self.id.value() as u64) << 16
+                | unit_address(self.group.as_char() as u32, self.unit_code),
+        )
+    }
+}
+
+impl HasDeviceId for HunterFan {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Hunter,
+            self.sub_type,
+            self.id
+                .iter()
+                .fold(0, |address, b| address << 8 | *b as u64),
+        )
+    }
+}
+
+impl HasDeviceId for Security1 {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Security1,
+            self.sub_type as u8,
+            self.id.value() as u64,
+        )
+    }
+}
+
+impl HasDeviceId for Security2 {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::Security2, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for Camera {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Camera,
+            self.sub_type,
+            self.house_code.as_char() as u64,
+        )
+    }
+}
+
+impl HasDeviceId for RemoteControl {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::Remote,
+            self.sub_type as u8,
+            self.id.value() as u64,
+        )
+    }
+}
+
+impl HasDeviceId for Bbq {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::BBQ, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for TempRain {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::TempRain, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for Temp {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::TEMP, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for Hum {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::HUM, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for TempHum {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::TempHum,
+            self.model.into(),
+            self.id.value() as u64,
+        )
+    }
+}
+
+impl HasDeviceId for TempHumBaro {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(
+            PacketType::TempHumBaro,
+            self.sub_type,
+            self.id.value() as u64,
+        )
+    }
+}
+
+impl HasDeviceId for Energy {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::ENERGY, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for Power {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::POWER, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for Weight {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::WEIGHT, self.sub_type, self.id.value() as u64)
+    }
+}
+
+impl HasDeviceId for WeatherStation {
+    fn device_id(&self) -> DeviceId {
+        DeviceId::new(PacketType::WEATHER, self.sub_type, self.id.value() as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn house_code() {
+        assert_eq!('C', "c".parse::<HouseCode>().unwrap());
+        assert!(HouseCode::new('Q').is_err());
+        assert!("AB".parse::<HouseCode>().is_err());
+        assert_eq!("P", HouseCode::new('P').unwrap().to_string());
+    }
+
+    #[test]
+    fn unit_code() {
+        assert_eq!(16, "16".parse::<UnitCode>().unwrap());
+        assert!(UnitCode::new(17).is_err());
+        assert!("x".parse::<UnitCode>().is_err());
+    }
+
+    #[test]
+    fn ids_in_hex() {
+        assert_eq!("1A2B", SensorId::from(0x1A2B).to_string());
+        assert_eq!(0x1A2B, "0x1a2b".parse::<SensorId>().unwrap());
+        assert_eq!("03F2A1", RemoteId::from(0x03F2A1).to_string());
+        assert_eq!(0x03F2A1, "3F2A1".parse::<RemoteId>().unwrap());
+    }
+
+    #[test]
+    fn device_ids() {
+        use crate::trx_command::{parse_message, ReceivedCommand};
+
+        let msg = parse_message(&[0x52, 0x01, 0x00, 0x2F, 0x01, 0x00, 0xD5, 0x2D, 0x01, 0x69]);
+        if let Ok(ReceivedCommand::ProtocolMessage(msg)) = msg {
+            let id = msg.device_id().unwrap();
+            assert_eq!(0x2F01, id.address());
+            assert_eq!("52/01/2F01", id.to_string());
+        } else {
+            panic!("Expected protocol message, got {:?}", msg);
+        }
+
+        // Units of the same house code are different devices
+        let b5 = parse_message(&[0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70]).unwrap();
+        let b6 = parse_message(&[0x10, 0x01, 0x00, 0x42, 0x06, 0x01, 0x70]).unwrap();
+        match (b5, b6) {
+            (ReceivedCommand::ProtocolMessage(b5), ReceivedCommand::ProtocolMessage(b6)) => {
+                assert_eq!(0x4205, b5.device_id().unwrap().address());
+                assert_ne!(b5.device_id(), b6.device_id());
+            }
+            other => panic!("Expected protocol messages, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Stable JSON representation of the protocol messages, so applications in other
+//! languages can consume the same events.
+//!
+//! A message is an object tagged with its packet type in `"type"`, named as the
+//! `ProtocolMessage` variants. The sub type is in `"sub_type"`, or `"model"` for
+//! sensors, named as the Rust enum variants. The other fields are named and scaled
+//! as the struct fields, with ids and codes as numbers and house codes as letters:
+//!
+//! ```json
+//! {"type":"TempHum","model":"Thgn122","id":12033,"temp":21.3,"humidity":45,
+//!  "humidity_status":"Comfort","battery":"Ok","rssi":6}
+//! ```
+//!
+//! Names are only changed in new major versions.
+use crate::{trx_command::ProtocolMessage, Result};
+
+impl ProtocolMessage {
+    /// Serializes the message in the JSON representation described in `rfxtrx433::json`
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Parses a message in the JSON representation described in `rfxtrx433::json`
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        trx_command::ProtocolMessage, Lighting1Command, Lighting1SubType, ModePreset, Protocols3,
+    };
+
+    #[test]
+    fn json_round_trip() {
+        let json = r#"{"type":"Lighting1","sub_type":"Arc","house_code":"B","unit_code":5,
+            "command":"On","rssi":7}"#;
+        let msg = ProtocolMessage::from_json(json).unwrap();
+        if let ProtocolMessage::Lighting1(msg) = &msg {
+            assert_eq!(Lighting1SubType::Arc, msg.sub_type);
+            assert_eq!('B', msg.house_code);
+            assert_eq!(5, msg.unit_code);
+            assert_eq!(Lighting1Command::On, msg.command);
+        } else {
+            panic!("Expected Lighting1, got {:?}", msg);
+        }
+        let json = msg.to_json().unwrap();
+        assert!(json.starts_with(r#"{"type":"Lighting1","sub_type":"Arc","house_code":"B""#));
+        assert!(ProtocolMessage::from_json(r#"{"type":"Lighting1","house_code":"Z"}"#).is_err());
+    }
+
+    #[test]
+    fn protocols_by_name() {
+        let preset = ModePreset::weather_sensors();
+        let json = serde_json::to_string(&preset).unwrap();
+        assert!(json.contains(r#""protos_3":["OREGON"]"#));
+        assert_eq!(preset, serde_json::from_str::<ModePreset>(&json).unwrap());
+
+        let protos: Protocols3 = serde_json::from_str(r#"["X10","AC"]"#).unwrap();
+        assert_eq!(Protocols3::X10 | Protocols3::AC, protos);
+        assert!(serde_json::from_str::<Protocols3>(r#"["NOPE"]"#).is_err());
+    }
+}
@@ -35,35 +35,109 @@
 
 #![warn(missing_docs)]
 
-use log::{debug, error, trace};
+use log::{debug, error, info, trace, warn};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime},
+};
 
 /// Result type used by the library
 pub type Result<T> = std::result::Result<T, TRXError>;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+mod dedup;
+mod display;
+#[cfg(feature = "json")]
+pub mod domoticz;
+mod duty_cycle;
 mod error;
+mod filter;
+pub mod frame;
+#[cfg(feature = "json")]
+pub mod homeassistant;
+mod ids;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "test-util")]
+mod mock;
 mod protocols;
+mod record;
+mod registry;
+mod runtime;
+mod scene;
 mod trx_command;
+#[cfg(feature = "uom")]
+mod units;
+mod weather;
 
+pub use builder::RFXtrx433Builder;
+pub use duty_cycle::DutyCycleOptions;
 pub use error::TRXError;
-pub use protocols::{Protocols1, Protocols2, Protocols3, Protocols4};
+pub use filter::Filter;
+pub use ids::{DeviceId, HasDeviceId, HouseCode, RemoteId, SensorId, UnitCode};
+#[cfg(feature = "test-util")]
+pub use mock::MockRfxtrx;
+pub use protocols::{
+    ModePreset, Protocols1, Protocols2, Protocols3, Protocols4, Protocols868_1, Protocols868_2,
+    Protocols868_3, Protocols868_4,
+};
+pub use record::{Direction, RawFrame, ReplayTransport};
+pub use registry::{DeviceRecord, DeviceRegistry};
+#[cfg(feature = "async-std")]
+pub use runtime::AsyncStdTransport;
+pub use scene::{Scene, SceneCommand, SceneStep};
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     select,
-    sync::mpsc::{
-        channel as bounded_channel, unbounded_channel, Receiver as BoundedReceiver,
-        Sender as BoundedSender, UnboundedReceiver, UnboundedSender,
+    sync::{
+        broadcast,
+        mpsc::{
+            channel as bounded_channel, error::TrySendError, unbounded_channel,
+            Receiver as BoundedReceiver, Sender as BoundedSender, UnboundedReceiver,
+            UnboundedSender,
+        },
+        oneshot, watch,
     },
 };
 use tokio_serial::SerialPortBuilderExt;
 use trx_command::ReceivedCommand;
-pub use trx_command::{Frequency, ProtocolMessage};
+pub use trx_command::{
+    celsius_to_fahrenheit, AsyncBaudRate, AsyncData, AsyncDataSubType, AsyncParity, AsyncPort,
+    AsyncPortCommand, BatteryStatus, Bbq, Camera, CameraCommand, ChimeSubType, Curtain,
+    CurtainCommand, EnabledProtocols, Energy, FWType, Fan, FanSubType, Forecast, Frequency,
+    Funkbus, FunkbusCommand, FunkbusSubType, HomeConfort, HomeConfortCommand, Hum, HumidityStatus,
+    HunterFan, HunterFanCommand, Lighting1, Lighting1Command, Lighting1SubType, Lighting2,
+    Lighting2Command, Lighting2SubType, Lighting3Command, Lighting4, Lighting5SubType,
+    Lighting6Command, Lighting6SubType, PacketHeader, PacketType, Power, ProtocolMessage,
+    RawPulses, RemoteControl, RemoteSubType, RfyCommand, RfyRemote, RfySubType, Rssi, Security1,
+    Security1Status, Security1SubType, Security2, SignalQuality, Temp, TempHum, TempHumBaro,
+    TempHumModel, TempRain, TemperatureUnit, Thermostat1Mode, Thermostat1Status,
+    Thermostat1SubType, Thermostat3Command, Thermostat3SubType, TransmitError, TransmitResult,
+    Undecoded, UndecodedProtocol, WeatherStation, Weight,
+};
+pub use weather::{dew_point, heat_index, wind_chill};
 
 const MESSAGE_QUEUE_LEN: usize = 100;
+/// Sequence number reserved for the watchdog and reconnect commands, so their
+/// responses can be told apart from the ones to user commands
+const INTERNAL_SEQNBR: trx_command::SequenceNumber = 0xFF;
+/// Time to wait for the device to respond to a command
+const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+/// Time to wait after a reset, the device needs at least 500 ms
+const RESET_DELAY: Duration = Duration::from_millis(1000);
+/// Input is discarded until nothing has been received for this long
+const DISCARD_QUIET_TIME: Duration = Duration::from_millis(50);
+/// Sent by a genuine device when the receiver is started
+const COPYRIGHT: &str = "Copyright RFXCOM";
+/// The device sends one message per stored remote, the list is done when it goes quiet
+const REMOTE_LIST_QUIET_TIME: std::time::Duration = std::time::Duration::from_millis(500);
 
 ///
 /// Tries to read a message from the serial port, if a message with size=0 is received,
 /// None is returned.
-async fn read_message(sp: &mut tokio_serial::SerialStream) -> Result<Option<Vec<u8>>> {
+async fn read_message(sp: &mut BoxedTransport) -> Result<Option<Vec<u8>>> {
     let mut buffer = Vec::with_capacity(255);
 
     // First byte is the size
@@ -85,38 +159,164 @@ async fn read_message(sp: &mut tokio_serial::SerialStream) -> Result<Option<Vec<
     Ok(Some(buffer))
 }
 
+/// Reads and drops the bytes the device sends until it goes quiet, e.g. the
+/// partial frames sent while it resets
+async fn discard_input(sp: &mut BoxedTransport) -> Result<()> {
+    let mut buffer = [0u8; 256];
+    loop {
+        match runtime::timeout(DISCARD_QUIET_TIME, sp.read(&mut buffer)).await {
+            // Closed, the next read reports it
+            Ok(Ok(0)) => return Ok(()),
+            Ok(Ok(len)) => trace!("Discarding {:02X?}", &buffer[..len]),
+            Ok(Err(e)) => return Err(e.into()),
+            Err(_) => return Ok(()),
+        }
+    }
+}
+
+/// Channels between the serial port task and the device structs
+struct SerialChannels {
+    to_serial_rx: UnboundedReceiver<Vec<u8>>,
+    // Requests to discard the input, answered when it's done
+    discard_rx: UnboundedReceiver<oneshot::Sender<()>>,
+    shutdown_rx: oneshot::Receiver<()>,
+    interface_msg_tx: BoundedSender<trx_command::InterfaceMessage>,
+    protocol_msg_tx: BoundedSender<ReceivedMessage>,
+    broadcast_tx: broadcast::Sender<ReceivedMessage>,
+    alive_tx: watch::Sender<Instant>,
+    events_tx: broadcast::Sender<ConnectionEvent>,
+    // Last set mode written, replayed when reconnecting
+    last_set_mode: Option<Vec<u8>>,
+    recorder: Option<record::Recorder>,
+    raw_tx: broadcast::Sender<RawFrame>,
+    temperature_unit: TemperatureUnit,
+    registry: Option<DeviceRegistry>,
+    dedup: Option<dedup::Deduplicator>,
+    filtered: filter::FilteredSubscribers,
+}
+
+impl SerialChannels {
+    /// Records and taps a raw frame
+    fn raw_frame(&mut self, direction: Direction, frame: &[u8]) {
+        let timestamp = SystemTime::now();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(direction, timestamp, frame);
+        }
+        if self.raw_tx.receiver_count() > 0 {
+            let _ = self.raw_tx.send(RawFrame {
+                direction,
+                timestamp,
+                data: frame.to_vec(),
+            });
+        }
+    }
+}
+
+/// Why the serial port task stopped
+enum SerialPortExit {
+    /// The connection was closed by the user
+    Closed,
+    /// Reading or writing the serial port failed
+    Failed(TRXError),
+}
+
 ///
 /// Listens for serial port messages
-async fn serial_port(
-    mut sp: tokio_serial::SerialStream,
-    mut to_serial_rx: UnboundedReceiver<Vec<u8>>,
-    interface_msg_tx: BoundedSender<trx_command::InterfaceMessage>,
-    protocol_msg_tx: BoundedSender<trx_command::ProtocolMessage>,
-) -> Result<()> {
+async fn serial_port(sp: &mut BoxedTransport, channels: &mut SerialChannels) -> SerialPortExit {
+    match serial_port_loop(sp, channels).await {
+        Ok(()) => SerialPortExit::Closed,
+        Err(e) => SerialPortExit::Failed(e),
+    }
+}
+
+async fn serial_port_loop(sp: &mut BoxedTransport, channels: &mut SerialChannels) -> Result<()> {
     loop {
         select! {
-            msg = to_serial_rx.recv() => match msg {
+            msg = channels.to_serial_rx.recv() => match msg {
                 // Shutdown if the channel is closed
                 None => return Ok(()),
                 Some(msg) => {
                     trace!("Sending {:02X?}", msg);
+                    if trx_command::is_set_mode(&msg) {
+                        channels.last_set_mode = Some(msg.clone());
+                    }
+                    channels.raw_frame(Direction::Sent, &msg);
                     sp.write_all(&msg).await?;
                 },
             },
-            msg = read_message(&mut sp) => match msg {
-                Ok(Some(msg)) => {
-                    match trx_command::parse_message(&msg) {
-                        Ok(ReceivedCommand::InterfaceMessage(msg)) => {
-                            interface_msg_tx.send(msg).await
-                                .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+            Some(done) = channels.discard_rx.recv() => {
+                // Also drops the frame being read, it may be a partial one
+                discard_input(sp).await?;
+                let _ = done.send(());
+            },
+            _ = &mut channels.shutdown_rx => {
+                // Write what's already queued before closing
+                while let Ok(msg) = channels.to_serial_rx.try_recv() {
+                    trace!("Sending {:02X?}", msg);
+                    channels.raw_frame(Direction::Sent, &msg);
+                    sp.write_all(&msg).await?;
+                }
+                sp.flush().await?;
+                debug!("Serial port closed");
+                return Ok(());
+            },
+            msg = read_message(sp) => match msg {
+                Ok(Some(data)) => {
+                    let timestamp = SystemTime::now();
+                    let instant = Instant::now();
+                    let mut frame = vec![data.len() as u8];
+                    frame.extend_from_slice(&data);
+                    if channels.recorder.is_some() || channels.raw_tx.receiver_count() > 0 {
+                        channels.raw_frame(Direction::Received, &frame);
+                    }
+                    let internal_response = data.get(2) == Some(&INTERNAL_SEQNBR);
+                    match trx_command::parse_packet(&data) {
+                        Ok((_, ReceivedCommand::InterfaceMessage(msg))) if internal_response => {
+                            trace!("Received internal response {:?}", msg);
+                            // The answer to the watchdog's status request shows the
+                            // device is alive, other frames may be buffered or noise
+                            if let trx_command::InterfaceMessage::Status { .. } = msg {
+                                let _ = channels.alive_tx.send(instant);
                             }
-                        Ok(ReceivedCommand::ProtocolMessage(msg)) => {
-                            protocol_msg_tx.send(msg).await
+                        }
+                        Ok((_, ReceivedCommand::InterfaceMessage(msg))) => {
+                            channels.interface_msg_tx.send(msg).await
                                 .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+                            }
+                        Ok((header, ReceivedCommand::ProtocolMessage(message))) => {
+                            let msg = ReceivedMessage {
+                                message,
+                                header,
+                                raw: frame,
+                                timestamp,
+                                instant,
+                                temperature_unit: channels.temperature_unit,
+                            };
+                            if let Some(registry) = &channels.registry {
+                                registry.record(&msg);
+                            }
+                            if channels.dedup.as_mut().is_some_and(|dedup| dedup.is_repeat(&msg)) {
+                                trace!("Dropping repeated {:02X?}", msg.raw);
+                                continue;
+                            }
+                            // Fails if there are no subscribers, which is fine
+                            let _ = channels.broadcast_tx.send(msg.clone());
+                            channels.filtered.send(&msg);
+                            // Don't block the serial port if only subscribers read messages
+                            match channels.protocol_msg_tx.try_send(msg) {
+                                Ok(()) => {},
+                                Err(TrySendError::Full(msg)) => {
+                                    warn!("Message queue full, dropping {:?}", msg);
+                                    let _ = channels.events_tx.send(ConnectionEvent::QueueOverflow);
+                                },
+                                Err(TrySendError::Closed(_)) => {},
+                            }
                             },
 
                         Err(e) => {
                             error!("Parsing error {}", e);
+                            let event = ConnectionEvent::ParseError(e.to_string());
+                            let _ = channels.events_tx.send(event);
                         }
                     };
                 },
@@ -128,98 +328,842 @@ async fn serial_port(
     }
 }
 
+/// Runs the serial port task, reopening the port by serial number when it fails
+async fn serial_port_reconnecting(
+    mut sp: BoxedTransport,
+    mut channels: SerialChannels,
+    port: Port,
+    baud_rate: u32,
+    options: ReconnectOptions,
+) -> Result<()> {
+    loop {
+        match serial_port(&mut sp, &mut channels).await {
+            SerialPortExit::Closed => return Ok(()),
+            SerialPortExit::Failed(e) => error!("Serial port failed: {}", e),
+        }
+        let _ = channels.events_tx.send(ConnectionEvent::Disconnected);
+
+        let mut delay = options.initial_delay;
+        let mut attempt = 0;
+        sp = loop {
+            attempt += 1;
+            let _ = channels
+                .events_tx
+                .send(ConnectionEvent::Reconnecting { attempt, delay });
+            select! {
+                _ = runtime::sleep(delay) => {},
+                _ = &mut channels.shutdown_rx => return Ok(()),
+            }
+            match reopen(&port, baud_rate, channels.last_set_mode.as_deref()).await {
+                Ok(sp) => break sp,
+                Err(e) => {
+                    warn!("Reconnect attempt {} failed: {}", attempt, e);
+                    delay = std::cmp::min(delay * 2, options.max_delay);
+                }
+            }
+        };
+        info!("Reconnected to {:?}", port);
+        let _ = channels.events_tx.send(ConnectionEvent::Connected);
+    }
+}
+
+/// Opens `port` and replays reset, set mode and start receiver
+async fn reopen(port: &Port, baud_rate: u32, set_mode: Option<&[u8]>) -> Result<BoxedTransport> {
+    let mut sp = port.open(baud_rate).await?;
+    // Responses use the internal sequence number, so they are dropped
+    sp.write_all(&trx_command::reset(INTERNAL_SEQNBR)).await?;
+    runtime::sleep(RESET_DELAY).await;
+    if let Some(set_mode) = set_mode {
+        let mut set_mode = set_mode.to_vec();
+        set_mode[3] = INTERNAL_SEQNBR;
+        sp.write_all(&set_mode).await?;
+    }
+    sp.write_all(&trx_command::start_receiver(INTERNAL_SEQNBR))
+        .await?;
+    Ok(sp)
+}
+
+/// Finds the port name of the USB device plugged in at `location`
+fn find_usb_port(location: &serialport::Location) -> Result<String> {
+    for sp in serialport::available_ports()? {
+        if let serialport::SerialPortType::UsbPort(type_info) = sp.port_type {
+            if type_info.location.as_ref() == Some(location) {
+                return Ok(sp.port_name);
+            }
+        }
+    }
+    Err(TRXError::DeviceAtLocationNotFound(location.to_string()))
+}
+
+/// FTDI vendor id, used by the RFXCOM hardware
+const FTDI_VID: u16 = 0x0403;
+/// FT232R, used by the RFXtrx433E, and FT230X product ids
+const FTDI_PIDS: [u16; 2] = [0x6001, 0x6015];
+
+/// Serial port that looks like a RFXtrx device, see `list_candidate_ports`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CandidatePort {
+    /// Port name, e.g. /dev/ttyUSB0, to pass to `RFXtrx433::new_from_serial_port`
+    pub port_name: String,
+    /// USB serial number, to pass to `RFXtrx433::new_from_serial_number`
+    pub serial_number: Option<String>,
+    /// USB product string
+    pub product: Option<String>,
+}
+
+/// Returns the serial ports that look like RFXtrx devices, i.e. have the FTDI
+/// vendor and product id or "RFXtrx" in the product string.
+pub fn list_candidate_ports() -> Result<Vec<CandidatePort>> {
+    let candidates = serialport::available_ports()?
+        .into_iter()
+        .filter_map(|sp| match sp.port_type {
+            serialport::SerialPortType::UsbPort(info)
+                if is_candidate(info.vid, info.pid, info.product.as_deref()) =>
+            {
+                Some(CandidatePort {
+                    port_name: sp.port_name,
+                    serial_number: info.serial_number,
+                    product: info.product,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    Ok(candidates)
+}
+
+fn is_candidate(vid: u16, pid: u16, product: Option<&str>) -> bool {
+    (vid == FTDI_VID && FTDI_PIDS.contains(&pid)) || product.is_some_and(|p| p.contains("RFXtrx"))
+}
+
+/// Finds the port name of the device with `serial`
+fn find_port(serial: &str) -> Result<String> {
+    let serialports = serialport::available_ports()?;
+    trace!("Searching for serial {} in serialports", serial);
+
+    for sp in serialports {
+        trace!("Checking for serial ({}) in {:?}", serial, sp);
+        if let serialport::SerialPortType::UsbPort(type_info) = sp.port_type {
+            if Some(serial) == type_info.serial_number.as_deref() {
+                return Ok(sp.port_name);
+            }
+        }
+    }
+    Err(TRXError::DeviceWithSerialNotFound(format!(
+        "Serial number {}",
+        serial
+    )))
+}
+
+fn open_port(port: &str, baud_rate: u32) -> Result<tokio_serial::SerialStream> {
+    Ok(tokio_serial::new(port, baud_rate).open_native_async()?)
+}
+
+/// Byte stream the device is connected over, e.g. a serial port or TCP socket
+pub trait Transport: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send + 'static> Transport for T {}
+
+type BoxedTransport = Box<dyn Transport>;
+
+/// How to connect to the device, again when reconnecting
+#[derive(Clone, Debug)]
+enum Port {
+    SerialNumber(String),
+    UsbPath(serialport::Location),
+    Path(String),
+    Tcp(String),
+}
+
+impl Port {
+    async fn open(&self, baud_rate: u32) -> Result<BoxedTransport> {
+        let path = match self {
+            Port::SerialNumber(serial) => find_port(serial)?,
+            Port::UsbPath(location) => find_usb_port(location)?,
+            Port::Path(path) => path.clone(),
+            Port::Tcp(addr) => return runtime::connect_tcp(addr).await,
+        };
+        Ok(Box::new(open_port(&path, baud_rate)?))
+    }
+}
+
+/// Backoff between reconnect attempts, see `RFXtrx433Builder::reconnect`
+#[derive(Clone, Copy, Debug)]
+pub struct ReconnectOptions {
+    /// Delay before the first attempt, doubled for every failed attempt
+    pub initial_delay: Duration,
+    /// Longest delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for ReconnectOptions {
+    fn default() -> Self {
+        ReconnectOptions {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Startup settings for `RFXtrx433::initialize`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InitOptions {
+    /// Frequency set along with `mode`
+    pub frequency: trx_command::Frequency,
+    /// Protocols to enable, the mode stored in the device is kept if `None`
+    pub mode: Option<ModePreset>,
+    /// Saves `mode` in the device, so it's kept after a power cycle
+    pub save_mode: bool,
+}
+
+/// Learning window of self-learning switches, see `Sender::pair_lighting2`
+#[derive(Clone, Copy, Debug)]
+pub struct PairingOptions {
+    /// How long to keep sending, most switches learn for about 10 seconds
+    pub window: Duration,
+    /// Time between the commands
+    pub interval: Duration,
+}
+
+impl Default for PairingOptions {
+    fn default() -> Self {
+        PairingOptions {
+            window: Duration::from_secs(10),
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Progress of pairing, passed to the callback after every command sent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PairingProgress {
+    /// Number of commands sent
+    pub attempt: u32,
+    /// Time since pairing started
+    pub elapsed: Duration,
+    /// Time left of the learning window
+    pub remaining: Duration,
+}
+
+/// How many times radio commands are sent, see `Sender::set_repeat`.
+///
+/// Many receivers miss single transmissions, the remotes usually send
+/// every command a few times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RepeatPolicy {
+    /// Number of times to send every command, 0 is the same as 1
+    pub count: u8,
+    /// Time between the repeats, after the previous one was acknowledged
+    pub delay: Duration,
+}
+
+impl Default for RepeatPolicy {
+    fn default() -> Self {
+        RepeatPolicy {
+            count: 1,
+            delay: Duration::from_millis(100),
+        }
+    }
+}
+
+/// A `Sender` using another repeat policy, see `Sender::with_repeat`
+pub struct WithRepeat<'a> {
+    sender: &'a mut Sender,
+    previous: RepeatPolicy,
+}
+
+impl std::ops::Deref for WithRepeat<'_> {
+    type Target = Sender;
+
+    fn deref(&self) -> &Sender {
+        self.sender
+    }
+}
+
+impl std::ops::DerefMut for WithRepeat<'_> {
+    fn deref_mut(&mut self) -> &mut Sender {
+        self.sender
+    }
+}
+
+impl Drop for WithRepeat<'_> {
+    fn drop(&mut self) {
+        self.sender.repeat = self.previous;
+    }
+}
+
+/// Changes to the connection to the device, see `Sender::connection_events`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectionEvent {
+    /// The serial port was reopened and the mode restored.
+    ///
+    /// Not sent for the first connection, that is done when the device is created.
+    Connected,
+    /// The serial port failed
+    Disconnected,
+    /// Waiting `delay` before reconnect attempt `attempt`
+    Reconnecting {
+        /// Attempt number, starting at 1
+        attempt: u32,
+        /// Delay before the attempt
+        delay: Duration,
+    },
+    /// A message from the device couldn't be parsed. Unknown sub types and commands
+    /// aren't errors, they are delivered as `ProtocolMessage::NotParsed`
+    ParseError(String),
+    /// A protocol message was dropped since the message queue was full
+    QueueOverflow,
+}
+
 /// This structs owns the serial port and provides the functions to configure the RFXtrx433 device.
+///
+/// The configuration and transmit functions are provided by `Sender`, which this struct
+/// dereferences to. Use `split` to send and receive from different tasks.
 pub struct RFXtrx433 {
+    sender: Sender,
+    receiver: Receiver,
+}
+
+/// Configures the device and transmits commands, see `RFXtrx433::split`
+pub struct Sender {
     seqnbr: trx_command::SequenceNumber,
     to_serial_tx: UnboundedSender<Vec<u8>>,
+    discard_tx: UnboundedSender<oneshot::Sender<()>>,
     interface_msg_rx: BoundedReceiver<trx_command::InterfaceMessage>,
-    protocol_msg_rx: BoundedReceiver<trx_command::ProtocolMessage>,
+    // Blyss receivers ignore repeated frames, so every Lighting6 device id
+    // needs its own rolling (command sequence number, rolling code)
+    lighting6_counters: HashMap<RemoteId, (u8, u8)>,
+    // Transmit power sent with every set mode, see set_transmit_power
+    xmitpwr: u8,
+    // Time the device last answered an internal status request, used by the watchdog
+    alive_rx: watch::Receiver<Instant>,
+    // Only used to create event subscribers
+    events_tx: broadcast::Sender<ConnectionEvent>,
+    command_timeout: Duration,
+    repeat: RepeatPolicy,
+    duty_cycle: Option<duty_cycle::DutyCycleLimiter>,
+    shutdown_tx: oneshot::Sender<()>,
+    serial_task: runtime::JoinHandle<Result<()>>,
+}
+
+/// Receives protocol messages from the device, see `RFXtrx433::split`
+pub struct Receiver {
+    protocol_msg_rx: BoundedReceiver<ReceivedMessage>,
+    // Only used to create subscribers
+    broadcast_tx: broadcast::Sender<ReceivedMessage>,
+    filtered: filter::FilteredSubscribers,
+    // Only used to create raw frame subscribers
+    raw_tx: broadcast::Sender<RawFrame>,
+    read_timeout: Option<Duration>,
 }
 
 impl RFXtrx433 {
+    /// Returns a builder to create an instance with non default settings
+    pub fn builder() -> RFXtrx433Builder {
+        RFXtrx433Builder::default()
+    }
+
     /// Try to create an instance from a serial number.
     /// The function iterates over the available serial ports and tries to match the serial number.
     pub async fn new_from_serial_number(serial: &str) -> Result<Self> {
-        let serialports = serialport::available_ports()?;
-        trace!("Searching for serial {} in serialports", serial);
-
-        for sp in serialports {
-            trace!("Checking for serial ({}) in {:?}", serial, sp);
-            if let serialport::SerialPortType::UsbPort(type_info) = sp.port_type {
-                if Some(serial) == type_info.serial_number.as_deref() {
-                    return Self::new_from_serial_port(&sp.port_name).await;
-                }
-            }
-        }
-        Err(TRXError::DeviceWithSerialNotFound(format!(
-            "Serial number {}",
-            serial
-        )))
+        Self::builder().open_serial_number(serial).await
+    }
+
+    /// Scans the serial ports for RFXCOM hardware and connects to the first device
+    /// that answers a status request, see `list_candidate_ports`.
+    pub async fn new_auto() -> Result<Self> {
+        Self::builder().open_auto().await
+    }
+
+    /// Like `new_from_serial_number`, but waits up to `timeout` for the device to be
+    /// plugged in, e.g. for services started before the USB devices are enumerated.
+    pub async fn wait_for_serial_number(serial: &str, timeout: Duration) -> Result<Self> {
+        Self::builder()
+            .wait_for_serial_number(serial, timeout)
+            .await
+    }
+
+    /// Like `new_from_serial_number`, but the serial port is reopened if it fails,
+    /// see `RFXtrx433Builder::reconnect`.
+    pub async fn new_from_serial_number_reconnecting(
+        serial: &str,
+        options: ReconnectOptions,
+    ) -> Result<Self> {
+        Self::builder()
+            .reconnect(options)
+            .open_serial_number(serial)
+            .await
+    }
+
+    /// Create an instance from the physical USB port the device is plugged in at,
+    /// see `RFXtrx433Builder::open_usb_path`.
+    pub async fn new_from_usb_path(bus: &str, port_chain: &[u8]) -> Result<Self> {
+        Self::builder().open_usb_path(bus, port_chain).await
     }
 
     /// Create an instance from a serial port tty, e.g. /dev/ttyUSB0
     pub async fn new_from_serial_port(port: &str) -> Result<Self> {
-        // let s = serialport::SerialPortSettings {
-        //     baud_rate: 38400,
-        //     ..Default::default()
-        // };
-        let sp = tokio_serial::new(port, 38400).open_native_async()?;
+        Self::builder().open_serial_port(port).await
+    }
+
+    /// Create an instance from a TCP address, e.g. a device shared with ser2net
+    /// on "raspberrypi:4000". The framing is the same as over the serial port.
+    pub async fn new_from_tcp(addr: &str) -> Result<Self> {
+        Self::builder().open_tcp(addr).await
+    }
+
+    /// Creates an instance communicating over `transport`, e.g. a PTY or a bridge
+    pub async fn new_from_transport(transport: impl Transport) -> Result<Self> {
+        Self::builder().open_transport(transport).await
+    }
+
+    /// Creates the channels and spawns the serial port task. Reconnecting
+    /// requires a `port` to reopen.
+    fn open(settings: &RFXtrx433Builder, sp: BoxedTransport, port: Option<Port>) -> Result<Self> {
+        if settings.queue_len == 0 {
+            return Err(TRXError::InvalidArgument(
+                "The queue length must be at least 1".to_string(),
+            ));
+        }
+        if let Some(options) = &settings.duty_cycle {
+            options.validate()?;
+        }
         let (to_serial_tx, to_serial_rx) = unbounded_channel();
-        let (interface_msg_tx, interface_msg_rx) = bounded_channel(MESSAGE_QUEUE_LEN);
-        let (protocol_msg_tx, protocol_msg_rx) = bounded_channel(MESSAGE_QUEUE_LEN);
-        tokio::spawn(async move {
-            serial_port(sp, to_serial_rx, interface_msg_tx, protocol_msg_tx).await
-        });
+        let (discard_tx, discard_rx) = unbounded_channel();
+        let (interface_msg_tx, interface_msg_rx) = bounded_channel(settings.queue_len);
+        let (protocol_msg_tx, protocol_msg_rx) = bounded_channel(settings.queue_len);
+        let (broadcast_tx, _) = broadcast::channel(settings.queue_len);
+        let filtered = filter::FilteredSubscribers::new(settings.queue_len);
+        let (events_tx, _) = broadcast::channel(settings.queue_len);
+        let (raw_tx, _) = broadcast::channel(settings.queue_len);
+        let (alive_tx, alive_rx) = watch::channel(Instant::now());
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let mut channels = SerialChannels {
+            to_serial_rx,
+            discard_rx,
+            shutdown_rx,
+            interface_msg_tx,
+            protocol_msg_tx,
+            broadcast_tx: broadcast_tx.clone(),
+            alive_tx,
+            events_tx: events_tx.clone(),
+            last_set_mode: None,
+            recorder: settings
+                .record
+                .as_deref()
+                .map(record::Recorder::create)
+                .transpose()?,
+            raw_tx: raw_tx.clone(),
+            temperature_unit: settings.temperature_unit,
+            registry: settings.registry.clone(),
+            dedup: settings.dedup.map(dedup::Deduplicator::new),
+            filtered: filtered.clone(),
+        };
+        let serial_task = match (settings.reconnect, port) {
+            (Some(_), None) => {
+                return Err(TRXError::InvalidArgument(
+                    "Reconnecting needs a port that can be reopened".to_string(),
+                ))
+            }
+            (Some(options), Some(port)) => runtime::spawn(serial_port_reconnecting(
+                sp,
+                channels,
+                port,
+                settings.baud_rate,
+                options,
+            )),
+            (None, _) => runtime::spawn(async move {
+                let mut sp = sp;
+                match serial_port(&mut sp, &mut channels).await {
+                    SerialPortExit::Closed => Ok(()),
+                    SerialPortExit::Failed(e) => {
+                        let _ = channels.events_tx.send(ConnectionEvent::Disconnected);
+                        Err(e)
+                    }
+                }
+            }),
+        };
         Ok(Self {
-            seqnbr: 0,
-            to_serial_tx,
-            interface_msg_rx,
-            protocol_msg_rx,
+            sender: Sender {
+                seqnbr: 0,
+                to_serial_tx,
+                discard_tx,
+                interface_msg_rx,
+                lighting6_counters: HashMap::new(),
+                xmitpwr: 0,
+                alive_rx,
+                events_tx,
+                command_timeout: settings.command_timeout,
+                repeat: settings.repeat,
+                duty_cycle: settings.duty_cycle.map(duty_cycle::DutyCycleLimiter::new),
+                shutdown_tx,
+                serial_task,
+            },
+            receiver: Receiver {
+                protocol_msg_rx,
+                broadcast_tx,
+                filtered,
+                raw_tx,
+                read_timeout: settings.read_timeout,
+            },
         })
     }
 
+    /// Runs the startup sequence recommended by RFXCOM: reset, discard what was
+    /// received while the device reset, get status, set the mode if requested and
+    /// start the receiver.
+    ///
+    /// Returns the device status, after the mode is set.
+    pub async fn initialize(&mut self, options: InitOptions) -> Result<RFXtrx433Info> {
+        self.sender.reset().await?;
+        self.sender.discard_input().await?;
+        self.receiver.discard_queued();
+        let mut info = self.sender.get_status().await?;
+        if let Some(preset) = options.mode {
+            self.sender.apply_preset(options.frequency, preset).await?;
+            if options.save_mode {
+                self.sender.save().await?;
+            }
+            info = self.sender.get_status().await?;
+        }
+        self.sender.start_receiver().await?;
+        Ok(info)
+    }
+
+    /// Closes the connection, see `Sender::close`
+    pub async fn close(self) -> Result<()> {
+        self.sender.close().await
+    }
+
+    /// Splits the device into a sender and a receiver half, so one task can
+    /// transmit while another waits for messages.
+    pub fn split(self) -> (Sender, Receiver) {
+        (self.sender, self.receiver)
+    }
+
+    /// This function will wait for protocol messages from the device
+    pub async fn read_message(&mut self) -> Result<ReceivedMessage> {
+        self.receiver.read_message().await
+    }
+
+    /// Waits for a protocol message from the device, or returns `TRXError::Timeout`
+    /// if none is received within `timeout`.
+    pub async fn read_message_timeout(&mut self, timeout: Duration) -> Result<ReceivedMessage> {
+        self.receiver.read_message_timeout(timeout).await
+    }
+
+    /// Returns a stream of the protocol messages from the device
+    pub fn messages(&mut self) -> Messages<'_> {
+        self.receiver.messages()
+    }
+
+    /// Subscribes to the protocol messages from the device, see `Receiver::subscribe`
+    pub fn subscribe(&self) -> broadcast::Receiver<ReceivedMessage> {
+        self.receiver.subscribe()
+    }
+
+    /// Subscribes to the selected protocol messages, see `Receiver::subscribe_filtered`
+    pub fn subscribe_filtered(&self, filter: Filter) -> broadcast::Receiver<ReceivedMessage> {
+        self.receiver.subscribe_filtered(filter)
+    }
+
+    /// Subscribes to the raw frames, see `Receiver::raw_frames`
+    pub fn raw_frames(&self) -> broadcast::Receiver<RawFrame> {
+        self.receiver.raw_frames()
+    }
+}
+
+impl std::ops::Deref for RFXtrx433 {
+    type Target = Sender;
+
+    fn deref(&self) -> &Sender {
+        &self.sender
+    }
+}
+
+impl std::ops::DerefMut for RFXtrx433 {
+    fn deref_mut(&mut self) -> &mut Sender {
+        &mut self.sender
+    }
+}
+
+impl Sender {
+    /// Subscribes to connection events, to report the health of the device
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events_tx.subscribe()
+    }
+
+    /// Closes the connection, writing any pending commands to the device first.
+    ///
+    /// Returns the error that stopped the serial port task, if any.
+    pub async fn close(self) -> Result<()> {
+        debug!("Closing");
+        // The task may already have stopped, then the join reports why
+        let _ = self.shutdown_tx.send(());
+        runtime::join(self.serial_task).await?
+    }
+
+    fn send(&mut self, msg: Vec<u8>) -> Result<()> {
+        // Responses arriving after a command timed out would be taken as
+        // the response to this command
+        while let Ok(cmd) = self.interface_msg_rx.try_recv() {
+            debug!("Dropping late response {:?}", cmd);
+        }
+        self.to_serial_tx
+            .send(msg)
+            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))
+    }
+
+    /// Sends a transmit command and waits for the device to acknowledge it
+    async fn transmit(&mut self, msg: Vec<u8>) -> Result<TransmitResult> {
+        self.reserve_airtime(None).await?;
+        self.transmit_reserved(msg).await
+    }
+
+    /// Waits until the duty cycle allows transmitting for `airtime`, by default the
+    /// estimated time of a command
+    async fn reserve_airtime(&mut self, airtime: Option<Duration>) -> Result<()> {
+        let limiter = match &mut self.duty_cycle {
+            Some(limiter) => limiter,
+            None => return Ok(()),
+        };
+        let airtime = airtime.unwrap_or_else(|| limiter.airtime());
+        loop {
+            let wait = limiter.reserve(airtime, Instant::now())?;
+            if wait.is_zero() {
+                return Ok(());
+            }
+            debug!("Duty cycle limit reached, waiting {:?}", wait);
+            runtime::sleep(wait).await;
+        }
+    }
+
+    /// Transmit time left within the duty cycle, None without a limit,
+    /// see `RFXtrx433Builder::duty_cycle`
+    pub fn duty_cycle_remaining(&self) -> Option<Duration> {
+        self.duty_cycle
+            .as_ref()
+            .map(|limiter| limiter.remaining(Instant::now()))
+    }
+
+    /// `transmit` once the airtime is reserved
+    async fn transmit_reserved(&mut self, msg: Vec<u8>) -> Result<TransmitResult> {
+        self.send(msg)?;
+
+        let cmd = self.recv_interface_msg().await?;
+        trace!("Received command: {:02X?}", cmd);
+        match cmd {
+            trx_command::InterfaceMessage::TransmitResponse(response) => {
+                response.map_err(TRXError::TransmitFailed)
+            }
+            trx_command::InterfaceMessage::UnknownRfyRemote => Err(TRXError::UnknownRfyRemote),
+            cmd => Err(TRXError::UnexpectedMessage(format!(
+                "Expected transmit response, received {:?}",
+                cmd
+            ))),
+        }
+    }
+
+    /// Transmits `msg` as many times as the repeat policy says. Every repeat waits for
+    /// its acknowledgement, the result is the last one acknowledged. A transmit failure
+    /// is only returned if no repeat was acknowledged, other errors stop the repeats.
+    async fn transmit_repeated(&mut self, mut msg: Vec<u8>) -> Result<TransmitResult> {
+        let RepeatPolicy { count, delay } = self.repeat;
+        let mut result = None;
+        for repeat in 0..count.max(1) {
+            if repeat > 0 {
+                runtime::sleep(delay).await;
+                msg[3] = self.next_seqnbr();
+                trace!("Repeating transmit, {} of {}", repeat + 1, count);
+            }
+            match self.transmit(msg.clone()).await {
+                Ok(ack) => result = Some(Ok(ack)),
+                Err(e @ TRXError::TransmitFailed(_)) => {
+                    if !matches!(result, Some(Ok(_))) {
+                        result = Some(Err(e));
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        result.expect("at least one transmit")
+    }
+
+    /// Sets how many times radio commands are sent and the delay between them,
+    /// the default is to send them once.
+    ///
+    /// Pairing, programming and erasing remotes and raw pulse trains are never repeated.
+    pub fn set_repeat(&mut self, policy: RepeatPolicy) {
+        self.repeat = policy;
+    }
+
+    /// Returns the repeat policy, see `set_repeat`
+    pub fn repeat(&self) -> RepeatPolicy {
+        self.repeat
+    }
+
+    /// Uses `policy` for the commands sent through the returned guard, the repeat
+    /// policy is restored when it is dropped.
+    ///
+    /// ```no_run
+    /// # async fn example(rfx: &mut rfxtrx433::Sender) -> rfxtrx433::Result<()> {
+    /// use rfxtrx433::{Lighting2Command, Lighting2SubType, RemoteId, RepeatPolicy, UnitCode};
+    /// use std::time::Duration;
+    ///
+    /// let policy = RepeatPolicy { count: 3, delay: Duration::from_millis(250) };
+    /// rfx.with_repeat(policy)
+    ///     .send_lighting2(
+    ///         Lighting2SubType::Ac,
+    ///         RemoteId::from(0x0123_4567),
+    ///         UnitCode::new(1)?,
+    ///         Lighting2Command::On,
+    ///         0,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_repeat(&mut self, policy: RepeatPolicy) -> WithRepeat<'_> {
+        let previous = std::mem::replace(&mut self.repeat, policy);
+        WithRepeat {
+            sender: self,
+            previous,
+        }
+    }
+
+    /// Waits for the response to the command in flight
+    async fn recv_interface_msg(&mut self) -> Result<trx_command::InterfaceMessage> {
+        let cmd = runtime::timeout(self.command_timeout, self.interface_msg_rx.recv()).await?;
+        Self::check_interface_msg(cmd)
+    }
+
+    /// Sets how long to wait for the device to respond to a command before
+    /// returning `TRXError::Timeout`, the default is 5 seconds.
+    pub fn set_command_timeout(&mut self, timeout: Duration) {
+        self.command_timeout = timeout;
+    }
+
+    /// Turns error responses from the device into errors
+    fn check_interface_msg(
+        cmd: Option<trx_command::InterfaceMessage>,
+    ) -> Result<trx_command::InterfaceMessage> {
+        match cmd.ok_or(TRXError::Shutdown)? {
+            trx_command::InterfaceMessage::ExtError(code) => Err(TRXError::ExtError(code)),
+            trx_command::InterfaceMessage::WrongCommand(cmd) => Err(TRXError::WrongCommand(cmd)),
+            cmd => Ok(cmd),
+        }
+    }
+
     fn next_seqnbr(&mut self) -> trx_command::SequenceNumber {
         let n = self.seqnbr;
-        self.seqnbr = self.seqnbr.wrapping_add(1);
+        self.seqnbr = trx_command::following_seqnbr(n);
         n
     }
 
+    /// Starts a watchdog task that sends a status request every `interval`,
+    /// and reports if the device hasn't answered within `timeout`.
+    ///
+    /// The task stops when the returned receiver, or the device, is dropped.
+    pub fn start_watchdog(
+        &self,
+        interval: Duration,
+        timeout: Duration,
+    ) -> BoundedReceiver<HealthEvent> {
+        let (health_tx, health_rx) = bounded_channel(MESSAGE_QUEUE_LEN);
+        // A strong sender would keep the serial port task running after the device is dropped
+        let to_serial_tx = self.to_serial_tx.downgrade();
+        let alive_rx = self.alive_rx.clone();
+        runtime::spawn(async move {
+            loop {
+                let sent = Instant::now();
+                let sent_ok = to_serial_tx
+                    .upgrade()
+                    .is_some_and(|tx| tx.send(trx_command::get_status(INTERNAL_SEQNBR)).is_ok());
+                if !sent_ok {
+                    let _ = health_tx.send(HealthEvent::Disconnected).await;
+                    return;
+                }
+                runtime::sleep(timeout).await;
+                let event = if *alive_rx.borrow() >= sent {
+                    HealthEvent::Healthy
+                } else {
+                    warn!("No response from device within {:?}", timeout);
+                    HealthEvent::Unresponsive
+                };
+                if health_tx.send(event).await.is_err() {
+                    return;
+                }
+                runtime::sleep(interval.saturating_sub(timeout)).await;
+            }
+        });
+        health_rx
+    }
+
+    /// Sends a packet the library doesn't model, e.g. to try new firmware features.
+    ///
+    /// The length byte and sequence number are filled in. No response is awaited,
+    /// responses are delivered as messages if they can be parsed, and can also be
+    /// seen with `Receiver::raw_frames`.
+    ///
+    /// Raw packets aren't counted by the duty cycle limit, the library can't tell
+    /// which of them are transmitted.
+    pub fn send_raw(&mut self, packet_type: u8, sub_type: u8, payload: &[u8]) -> Result<()> {
+        let msg = trx_command::raw_packet(self.next_seqnbr(), packet_type, sub_type, payload)?;
+        debug!("Sending raw packet {:02X?}", msg);
+        self.send(msg)
+    }
+
     /// Sends a reset signal to the device
     pub async fn reset(&mut self) -> Result<()> {
         let cmd = trx_command::reset(self.next_seqnbr()).to_vec();
-        self.to_serial_tx
-            .send(cmd)
-            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+        self.send(cmd)?;
         // Need to sleep at least 500 ms after reset
         debug!("Sleeping after sending reset");
-        tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+        runtime::sleep(RESET_DELAY).await;
         Ok(())
     }
 
+    /// Drops the bytes received but not yet parsed into messages, and what the
+    /// device sends until it goes quiet
+    async fn discard_input(&mut self) -> Result<()> {
+        let (done_tx, done_rx) = oneshot::channel();
+        self.discard_tx
+            .send(done_tx)
+            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+        runtime::timeout(self.command_timeout, done_rx)
+            .await?
+            .map_err(|_| TRXError::Shutdown)
+    }
+
     /// Sends a get status signal to the device and waits for a response
     pub async fn get_status(&mut self) -> Result<RFXtrx433Info> {
         let msg = trx_command::get_status(self.next_seqnbr()).to_vec();
         debug!("sending get status");
-        self.to_serial_tx
-            .send(msg)
-            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+        self.send(msg)?;
 
-        let cmd = self
-            .interface_msg_rx
-            .recv()
-            .await
-            .ok_or(TRXError::Shutdown)?;
+        let cmd = self.recv_interface_msg().await?;
         debug!("Received get_status response");
         trace!("Received command: {:02X?}", cmd);
         if let trx_command::InterfaceMessage::Status {
             enabled_protocols,
             frequency,
-            ..
+            fw_version,
+            hw_version_major,
+            hw_version_minor,
+            output_power,
+            fw_type,
+            device_name,
         } = cmd
         {
             Ok(RFXtrx433Info {
                 frequency,
                 enabled_protocols,
+                fw_version,
+                fw_type,
+                hw_version_major,
+                hw_version_minor,
+                output_power,
+                device_name,
             })
         } else {
             Err(TRXError::UnexpectedMessage(format!(
@@ -230,22 +1174,56 @@ impl RFXtrx433 {
     }
 
     /// Starts the receiver and waits for confirmation.
-    pub async fn start_receiver(&mut self) -> Result<()> {
+    ///
+    /// Returns the copyright message sent by the device, an error is returned
+    /// if it isn't the expected RFXCOM copyright.
+    pub async fn start_receiver(&mut self) -> Result<String> {
         let msg = trx_command::start_receiver(self.next_seqnbr()).to_vec();
         debug!("Sending start_receiver");
-        self.to_serial_tx
-            .send(msg)
-            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+        self.send(msg)?;
 
-        let cmd = self
-            .interface_msg_rx
-            .recv()
-            .await
-            .ok_or(TRXError::Shutdown)?;
+        let cmd = self.recv_interface_msg().await?;
         debug!("Received start_receiver response");
         trace!("Received command: {:02X?}", cmd);
 
-        Ok(())
+        match cmd {
+            trx_command::InterfaceMessage::ReceiverStarted(copyright)
+                if copyright.starts_with(COPYRIGHT) =>
+            {
+                Ok(copyright)
+            }
+            trx_command::InterfaceMessage::ReceiverStarted(copyright) => {
+                Err(TRXError::InvalidCopyright(copyright))
+            }
+            cmd => Err(TRXError::UnexpectedMessage(format!(
+                "Expected receiver started, received {:?}",
+                cmd
+            ))),
+        }
+    }
+
+    /// Enables receiving of all protocols, e.g. to sniff for unknown devices.
+    ///
+    /// The setting isn't saved, the device returns to the saved mode after a reset.
+    pub async fn enable_all(&mut self) -> Result<()> {
+        let msg = trx_command::enable_all(self.next_seqnbr());
+        debug!("Sending enable_all");
+        self.send(msg)?;
+
+        let cmd = self.recv_interface_msg().await?;
+        debug!("Received enable_all response");
+        trace!("Received command: {:02X?}", cmd);
+
+        Ok(())
+    }
+
+    /// Disables receiving of all protocols, keeping the current frequency.
+    ///
+    /// The setting isn't saved, the device returns to the saved mode after a reset.
+    pub async fn disable_all(&mut self) -> Result<()> {
+        let frequency = self.get_status().await?.frequency;
+        debug!("Disabling all protocols");
+        self.set_mode_raw(frequency, [0; 4]).await
     }
 
     /// Sets the mode of the receiver, then calls save.
@@ -257,39 +1235,165 @@ impl RFXtrx433 {
         protos_3: Protocols3,
         protos_4: Protocols4,
     ) -> Result<()> {
+        self.set_mode_unsaved(frequency, protos_1, protos_2, protos_3, protos_4)
+            .await?;
+        self.save().await
+    }
+
+    /// Sets the mode of the receiver without saving it.
+    ///
+    /// Saving writes to the device flash, so prefer this when the mode is set at every startup.
+    /// The protocols are the 433 MHz ones, use `set_mode_868_unsaved` for 868 MHz devices.
+    pub async fn set_mode_unsaved(
+        &mut self,
+        frequency: trx_command::Frequency,
+        protos_1: Protocols1,
+        protos_2: Protocols2,
+        protos_3: Protocols3,
+        protos_4: Protocols4,
+    ) -> Result<()> {
+        if matches!(frequency, trx_command::Frequency::TrxType868)
+            && !(protos_1.is_empty()
+                && protos_2.is_empty()
+                && protos_3.is_empty()
+                && protos_4.is_empty())
+        {
+            return Err(TRXError::InvalidArgument(
+                "433 MHz protocols can't be enabled at 868 MHz, use set_mode_868".to_string(),
+            ));
+        }
         let msg = trx_command::set_mode(
             self.next_seqnbr(),
             frequency,
+            self.xmitpwr,
             protos_1,
             protos_2,
             protos_3,
             protos_4,
+        );
+        self.send_set_mode(msg).await
+    }
+
+    /// Sets the protocols of a `ModePreset` without saving it, see `set_mode_unsaved`.
+    pub async fn apply_preset(
+        &mut self,
+        frequency: trx_command::Frequency,
+        preset: ModePreset,
+    ) -> Result<()> {
+        debug!("Applying {:?}", preset);
+        self.set_mode_unsaved(
+            frequency,
+            preset.protos_1,
+            preset.protos_2,
+            preset.protos_3,
+            preset.protos_4,
         )
-        .to_vec();
+        .await
+    }
+
+    /// Sets the 868 MHz protocols of the receiver, then calls save.
+    pub async fn set_mode_868(
+        &mut self,
+        protos_1: Protocols868_1,
+        protos_2: Protocols868_2,
+        protos_3: Protocols868_3,
+        protos_4: Protocols868_4,
+    ) -> Result<()> {
+        self.set_mode_868_unsaved(protos_1, protos_2, protos_3, protos_4)
+            .await?;
+        self.save().await
+    }
+
+    /// Sets the 868 MHz protocols of the receiver without saving it.
+    pub async fn set_mode_868_unsaved(
+        &mut self,
+        protos_1: Protocols868_1,
+        protos_2: Protocols868_2,
+        protos_3: Protocols868_3,
+        protos_4: Protocols868_4,
+    ) -> Result<()> {
+        let msg = trx_command::set_mode_868(
+            self.next_seqnbr(),
+            self.xmitpwr,
+            protos_1,
+            protos_2,
+            protos_3,
+            protos_4,
+        );
+        self.send_set_mode(msg).await
+    }
+
+    async fn set_mode_raw(
+        &mut self,
+        frequency: trx_command::Frequency,
+        protos: [u8; 4],
+    ) -> Result<()> {
+        let msg = trx_command::set_mode_raw(self.next_seqnbr(), frequency, self.xmitpwr, protos);
+        self.send_set_mode(msg).await
+    }
+
+    async fn send_set_mode(&mut self, msg: Vec<u8>) -> Result<()> {
         debug!("Sending set_mode");
-        self.to_serial_tx
-            .send(msg)
-            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+        self.send(msg)?;
 
-        let cmd = self
-            .interface_msg_rx
-            .recv()
+        let cmd = self.recv_interface_msg().await?;
+        trace!("Received command: {:02X?}", cmd);
+
+        Ok(())
+    }
+
+    /// Sets the transmit power in dBm, used by RFXtrx868 and ProXL hardware.
+    ///
+    /// The allowed range is -18 to +10 dBm at 433 MHz and -18 to +13 dBm at 868 MHz.
+    /// The power is applied with the current mode and kept for later calls to
+    /// `set_mode`, it isn't saved.
+    pub async fn set_transmit_power(&mut self, dbm: i8) -> Result<()> {
+        let status = self.get_status().await?;
+        self.xmitpwr = trx_command::transmit_power(status.frequency, dbm)?;
+        debug!("Setting transmit power {} dBm", dbm);
+        self.set_mode_raw(status.frequency, status.enabled_protocols.to_bytes())
             .await
-            .ok_or(TRXError::Shutdown)?;
+    }
+
+    /// Gets the receive noise level of a ProXL device
+    pub async fn get_noise_level(&mut self) -> Result<u8> {
+        let msg = trx_command::noise_level(self.next_seqnbr(), None)?;
+        debug!("Sending get noise level");
+        self.noise_level(msg).await
+    }
+
+    /// Sets the receive noise level of a ProXL device, 0 - 15.
+    ///
+    /// A higher level makes the receiver less sensitive, which helps in noisy RF environments.
+    pub async fn set_noise_level(&mut self, level: u8) -> Result<()> {
+        let msg = trx_command::noise_level(self.next_seqnbr(), Some(level))?;
+        debug!("Sending set noise level {}", level);
+        self.noise_level(msg).await?;
+        Ok(())
+    }
+
+    async fn noise_level(&mut self, msg: Vec<u8>) -> Result<u8> {
+        self.send(msg)?;
+
+        let cmd = self.recv_interface_msg().await?;
         trace!("Received command: {:02X?}", cmd);
+        match cmd {
+            trx_command::InterfaceMessage::NoiseLevel(level) => Ok(level),
+            cmd => Err(TRXError::UnexpectedMessage(format!(
+                "Expected noise level response, received {:?}",
+                cmd
+            ))),
+        }
+    }
 
+    /// Saves the current mode to the device flash, so it is kept after a reset.
+    pub async fn save(&mut self) -> Result<()> {
         let msg = trx_command::save(self.next_seqnbr()).to_vec();
 
         debug!("Sending save");
-        self.to_serial_tx
-            .send(msg)
-            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))?;
+        self.send(msg)?;
 
-        let cmd = self
-            .interface_msg_rx
-            .recv()
-            .await
-            .ok_or(TRXError::Shutdown)?;
+        let cmd = self.recv_interface_msg().await?;
 
         debug!("Received save response");
         trace!("Received command: {:02X?}", cmd);
@@ -297,8 +1401,586 @@ impl RFXtrx433 {
         Ok(())
     }
 
-    /// This function will wait for protocol messages from the device
-    pub async fn read_message(&mut self) -> Result<trx_command::ProtocolMessage> {
+    /// Sends a Lighting1 command, used by X10, ARC and similar devices.
+    ///
+    /// `unit_code` is 1 - 16.
+    pub async fn send_lighting1(
+        &mut self,
+        sub_type: Lighting1SubType,
+        house_code: HouseCode,
+        unit_code: UnitCode,
+        command: Lighting1Command,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::lighting1(
+            self.next_seqnbr(),
+            sub_type,
+            house_code.as_char(),
+            unit_code.value(),
+            command,
+        )?;
+        debug!("Sending lighting1 {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Lighting2 command, used by AC, HomeEasy EU and similar self-learning switches.
+    ///
+    /// `id` is the 26 bit device address, `unit_code` 1 - 16 and `level` the dim level 0 - 15,
+    /// only used by the set level commands.
+    pub async fn send_lighting2(
+        &mut self,
+        sub_type: Lighting2SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: Lighting2Command,
+        level: u8,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::lighting2(
+            self.next_seqnbr(),
+            sub_type,
+            id.value(),
+            unit_code.value(),
+            command,
+            level,
+        )?;
+        debug!("Sending lighting2 {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Lighting3 command, used by Ikea Koppla.
+    ///
+    /// `system` is 1 - 16 and `channels` a bitmap where bit 0 - 9 selects channel 1 - 10.
+    pub async fn send_lighting3(
+        &mut self,
+        system: RemoteId,
+        channels: u16,
+        command: Lighting3Command,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::lighting3(self.next_seqnbr(), system.value(), channels, command)?;
+        debug!("Sending lighting3 {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Lighting5 command, used by LightwaveRF, Livolo, IT and similar devices.
+    ///
+    /// `id` is 24 bits. The meaning of `command` depends on the device type, for LightwaveRF
+    /// 0x00 is off, 0x01 on and 0x10 sets the dim `level` 0 - 31.
+    pub async fn send_lighting5(
+        &mut self,
+        sub_type: Lighting5SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: u8,
+        level: u8,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::lighting5(
+            self.next_seqnbr(),
+            sub_type,
+            id.value(),
+            unit_code.value(),
+            command,
+            level,
+        )?;
+        debug!("Sending lighting5 {:02X}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Pairs a self-learning AC, HomeEasy EU or similar switch with `id` and `unit_code`.
+    ///
+    /// Put the switch in learning mode first, usually by holding its learn button or
+    /// powering it on. On is sent every `options.interval` for the learning window and
+    /// `progress` is called after each command. Returns the number of commands sent.
+    pub async fn pair_lighting2(
+        &mut self,
+        sub_type: Lighting2SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        options: PairingOptions,
+        progress: impl FnMut(PairingProgress),
+    ) -> Result<u32> {
+        debug!("Pairing lighting2 {} unit {}", id, unit_code);
+        self.pair(options, progress, |seqnbr| {
+            trx_command::lighting2(
+                seqnbr,
+                sub_type,
+                id.value(),
+                unit_code.value(),
+                Lighting2Command::On,
+                0,
+            )
+        })
+        .await
+    }
+
+    /// Pairs a LightwaveRF switch or dimmer with `id` and `unit_code`,
+    /// see `pair_lighting2`.
+    pub async fn pair_lightwave_rf(
+        &mut self,
+        id: RemoteId,
+        unit_code: UnitCode,
+        options: PairingOptions,
+        progress: impl FnMut(PairingProgress),
+    ) -> Result<u32> {
+        debug!("Pairing LightwaveRF {} unit {}", id, unit_code);
+        self.pair(options, progress, |seqnbr| {
+            // 0x01 is on
+            trx_command::lighting5(
+                seqnbr,
+                Lighting5SubType::LightwaveRf,
+                id.value(),
+                unit_code.value(),
+                0x01,
+                0,
+            )
+        })
+        .await
+    }
+
+    /// Sends the frame from `build` for the learning window
+    async fn pair(
+        &mut self,
+        options: PairingOptions,
+        mut progress: impl FnMut(PairingProgress),
+        build: impl Fn(trx_command::SequenceNumber) -> Result<Vec<u8>>,
+    ) -> Result<u32> {
+        let start = Instant::now();
+        let mut attempt = 0;
+        loop {
+            let msg = build(self.next_seqnbr())?;
+            self.transmit(msg).await?;
+            attempt += 1;
+            let elapsed = start.elapsed();
+            progress(PairingProgress {
+                attempt,
+                elapsed,
+                remaining: options.window.saturating_sub(elapsed),
+            });
+            if elapsed + options.interval > options.window {
+                return Ok(attempt);
+            }
+            runtime::sleep(options.interval).await;
+        }
+    }
+
+    /// Sends a Lighting6 command, used by Blyss and Cuveo devices.
+    ///
+    /// `id` is 16 bits, `group_code` 'A' - 'P' and `unit_code` 1 - 5. The rolling counters
+    /// required by the receivers are kept per `id`, see `lighting6_counters`.
+    pub async fn send_lighting6(
+        &mut self,
+        sub_type: Lighting6SubType,
+        id: RemoteId,
+        group_code: HouseCode,
+        unit_code: UnitCode,
+        command: Lighting6Command,
+    ) -> Result<TransmitResult> {
+        let (cmnd_seqnbr, rolling_code) = self.lighting6_counters(id);
+        let msg = trx_command::lighting6(
+            self.next_seqnbr(),
+            sub_type,
+            id.value(),
+            group_code.as_char(),
+            unit_code.value(),
+            command,
+            cmnd_seqnbr,
+            rolling_code,
+        )?;
+        // The command sequence number runs 0 - 4
+        self.lighting6_counters
+            .insert(id, ((cmnd_seqnbr + 1) % 5, rolling_code.wrapping_add(1)));
+        debug!("Sending lighting6 {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Returns the command sequence number and rolling code the next Lighting6
+    /// command to `id` is sent with.
+    ///
+    /// The counters are only kept in memory, store them to continue where a
+    /// previous run stopped, see `set_lighting6_counters`.
+    pub fn lighting6_counters(&self, id: RemoteId) -> (u8, u8) {
+        *self.lighting6_counters.get(&id).unwrap_or(&(0, 0))
+    }
+
+    /// Sets the command sequence number, 0 - 4, and rolling code of the next
+    /// Lighting6 command to `id`, e.g. as stored from `lighting6_counters`.
+    pub fn set_lighting6_counters(
+        &mut self,
+        id: RemoteId,
+        cmnd_seqnbr: u8,
+        rolling_code: u8,
+    ) -> Result<()> {
+        if cmnd_seqnbr > 4 {
+            return Err(TRXError::InvalidArgument(format!(
+                "Command sequence number {} not in 0 - 4",
+                cmnd_seqnbr
+            )));
+        }
+        self.lighting6_counters
+            .insert(id, (cmnd_seqnbr, rolling_code));
+        Ok(())
+    }
+
+    /// Rings a Byron SX, SelectPlus or similar doorbell with the given melody.
+    ///
+    /// `id` is 16 bits.
+    pub async fn send_chime(
+        &mut self,
+        sub_type: ChimeSubType,
+        id: RemoteId,
+        sound: u8,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::chime(self.next_seqnbr(), sub_type, id.value(), sound)?;
+        debug!("Sending chime {:02X}", sound);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a RFY command, used by Somfy RTS blinds and awnings.
+    ///
+    /// `id` is 20 bits and `unit_code` 0 - 4, where 0 addresses all units. The
+    /// extended sub type allows unit codes 0 - 15.
+    pub async fn send_rfy(
+        &mut self,
+        sub_type: RfySubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: RfyCommand,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::rfy(
+            self.next_seqnbr(),
+            sub_type,
+            id.value(),
+            unit_code.value(),
+            command,
+        )?;
+        debug!("Sending rfy {:?}", command);
+        match command {
+            // Handled by the device itself, repeating would pair or erase again
+            RfyCommand::Program | RfyCommand::EraseThis | RfyCommand::EraseAll => {
+                self.transmit(msg).await
+            }
+            _ => self.transmit_repeated(msg).await,
+        }
+    }
+
+    /// Pairs a RFY remote with the device.
+    ///
+    /// Put the blind in programming mode with its original remote first.
+    pub async fn program_rfy_remote(
+        &mut self,
+        sub_type: RfySubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+    ) -> Result<TransmitResult> {
+        self.send_rfy(sub_type, id, unit_code, RfyCommand::Program)
+            .await
+    }
+
+    /// Erases a RFY remote stored in the device
+    pub async fn erase_rfy_remote(
+        &mut self,
+        sub_type: RfySubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+    ) -> Result<TransmitResult> {
+        self.send_rfy(sub_type, id, unit_code, RfyCommand::EraseThis)
+            .await
+    }
+
+    /// Erases all RFY remotes stored in the device
+    pub async fn erase_all_rfy_remotes(&mut self) -> Result<TransmitResult> {
+        self.send_rfy(
+            RfySubType::Rfy,
+            RemoteId::from(0),
+            UnitCode::new(0)?,
+            RfyCommand::EraseAll,
+        )
+        .await
+    }
+
+    /// Lists the RFY remotes stored in the device
+    pub async fn list_rfy_remotes(&mut self) -> Result<Vec<RfyRemote>> {
+        self.list_remotes(RfySubType::Rfy).await
+    }
+
+    /// Pairs an ASA remote with the device.
+    ///
+    /// Put the blind in programming mode with its original remote first.
+    pub async fn program_asa_remote(
+        &mut self,
+        id: RemoteId,
+        unit_code: UnitCode,
+    ) -> Result<TransmitResult> {
+        self.send_rfy(RfySubType::Asa, id, unit_code, RfyCommand::Program)
+            .await
+    }
+
+    /// Erases an ASA remote stored in the device
+    pub async fn erase_asa_remote(
+        &mut self,
+        id: RemoteId,
+        unit_code: UnitCode,
+    ) -> Result<TransmitResult> {
+        self.send_rfy(RfySubType::Asa, id, unit_code, RfyCommand::EraseThis)
+            .await
+    }
+
+    /// Erases all ASA remotes stored in the device
+    pub async fn erase_all_asa_remotes(&mut self) -> Result<TransmitResult> {
+        self.send_rfy(
+            RfySubType::Asa,
+            RemoteId::from(0),
+            UnitCode::new(0)?,
+            RfyCommand::EraseAll,
+        )
+        .await
+    }
+
+    /// Lists the ASA remotes stored in the device
+    pub async fn list_asa_remotes(&mut self) -> Result<Vec<RfyRemote>> {
+        self.list_remotes(RfySubType::Asa).await
+    }
+
+    async fn list_remotes(&mut self, sub_type: RfySubType) -> Result<Vec<RfyRemote>> {
+        let msg = trx_command::rfy(self.next_seqnbr(), sub_type, 0, 0, RfyCommand::ListRemotes)?;
+        debug!("Sending list {:?} remotes", sub_type);
+        self.send(msg)?;
+
+        let mut remotes = Vec::new();
+        while let Ok(cmd) =
+            runtime::timeout(REMOTE_LIST_QUIET_TIME, self.interface_msg_rx.recv()).await
+        {
+            match (sub_type, Self::check_interface_msg(cmd)?) {
+                (RfySubType::Asa, trx_command::InterfaceMessage::AsaRemoteList(remote)) => {
+                    remotes.push(remote)
+                }
+                (RfySubType::Rfy, trx_command::InterfaceMessage::RfyRemoteList(remote))
+                | (RfySubType::RfyExt, trx_command::InterfaceMessage::RfyRemoteList(remote)) => {
+                    remotes.push(remote)
+                }
+                (_, cmd) => trace!("Ignoring {:?} while listing remotes", cmd),
+            }
+        }
+        debug!("Received {} remotes", remotes.len());
+        Ok(remotes)
+    }
+
+    /// Sends a HomeConfort command, used by TEL-010 sockets.
+    ///
+    /// `id` is 19 bits, `house_code` 'A' - 'D' and `unit_code` 1 - 4.
+    pub async fn send_home_confort(
+        &mut self,
+        id: RemoteId,
+        house_code: HouseCode,
+        unit_code: UnitCode,
+        command: HomeConfortCommand,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::home_confort(
+            self.next_seqnbr(),
+            id.value(),
+            house_code.as_char(),
+            unit_code.value(),
+            command,
+        )?;
+        debug!("Sending home confort {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Hunter ceiling fan command.
+    pub async fn send_hunter_fan(
+        &mut self,
+        id: [u8; 6],
+        command: HunterFanCommand,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::hunter_fan(self.next_seqnbr(), id, command);
+        debug!("Sending hunter fan {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Security1 command, e.g. arm, disarm, panic or light on/off, as an X10 or
+    /// Meiantech security remote.
+    pub async fn send_security1(
+        &mut self,
+        sub_type: Security1SubType,
+        id: RemoteId,
+        status: Security1Status,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::security1(self.next_seqnbr(), sub_type, id.value(), status)?;
+        debug!("Sending security1 {:?}", status);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a pan/tilt command to an X10 Ninja camera mount
+    pub async fn send_camera(
+        &mut self,
+        house_code: HouseCode,
+        command: CameraCommand,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::camera(self.next_seqnbr(), house_code.as_char(), command)?;
+        debug!("Sending camera {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Emulates a button press on an ATI or Medion remote.
+    ///
+    /// `id` is 8 bits. Receivers treat frames with the same `toggle` as a held down
+    /// button, flip it between separate presses.
+    pub async fn send_remote(
+        &mut self,
+        sub_type: RemoteSubType,
+        id: RemoteId,
+        command: u8,
+        toggle: bool,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::remote(self.next_seqnbr(), sub_type, id.value(), command, toggle)?;
+        debug!("Sending remote {:02X}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Thermostat1 message as a Digimax thermostat.
+    ///
+    /// `id` is 16 bits, `temperature` the measured temperature 0 - 50 °C and `set_point`
+    /// the wanted temperature 5 - 45 °C.
+    pub async fn send_thermostat1(
+        &mut self,
+        sub_type: Thermostat1SubType,
+        id: RemoteId,
+        temperature: u8,
+        set_point: u8,
+        status: Thermostat1Status,
+        mode: Thermostat1Mode,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::thermostat1(
+            self.next_seqnbr(),
+            sub_type,
+            id.value(),
+            temperature,
+            set_point,
+            status,
+            mode,
+        )?;
+        debug!("Sending thermostat1 {} {}", temperature, set_point);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Sends a Thermostat3 command, used by Mertik controlled fireplaces.
+    pub async fn send_thermostat3(
+        &mut self,
+        sub_type: Thermostat3SubType,
+        unit_id: RemoteId,
+        command: Thermostat3Command,
+    ) -> Result<TransmitResult> {
+        let msg = trx_command::thermostat3(self.next_seqnbr(), sub_type, unit_id.value(), command)?;
+        debug!("Sending thermostat3 {:?}", command);
+        self.transmit_repeated(msg).await
+    }
+
+    /// Transmits a raw pulse train, for protocols the firmware doesn't support.
+    ///
+    /// `pulses` alternates pulse and gap durations in microseconds, at most 496
+    /// entries, and is sent `repeat` times.
+    pub async fn send_raw_pulses(&mut self, pulses: &[u16], repeat: u8) -> Result<TransmitResult> {
+        let mut frames = trx_command::raw_pulses(self.seqnbr, pulses, repeat)?;
+        debug!(
+            "Sending {} raw pulses in {} frames",
+            pulses.len(),
+            frames.len()
+        );
+        let pulse_time: u64 = pulses.iter().map(|&pulse| pulse as u64).sum();
+        let airtime = Duration::from_micros(pulse_time * repeat.max(1) as u64);
+        self.reserve_airtime(Some(airtime)).await?;
+        let mut last = frames.pop().expect("at least one raw frame");
+        for mut frame in frames {
+            frame[3] = self.next_seqnbr();
+            self.send(frame)?;
+        }
+        // The device only starts transmitting, and answers, after the last frame
+        last[3] = self.next_seqnbr();
+        self.transmit_reserved(last).await
+    }
+
+    /// Configures the async serial port of a RFXtrx433XL, and waits for the device
+    /// to acknowledge it.
+    ///
+    /// The device also reports its new configuration as a [`ProtocolMessage::AsyncPort`].
+    pub async fn configure_async_port(&mut self, config: AsyncPort) -> Result<TransmitResult> {
+        let msg = trx_command::async_port(self.next_seqnbr(), &config)?;
+        debug!("Sending async port configuration {:?}", config);
+        // Not sent over the air, so no airtime to reserve
+        self.transmit_reserved(msg).await
+    }
+
+    /// Sends data out of the async serial port of a RFXtrx433XL. Every frame waits
+    /// for its acknowledgement, the result is the last one.
+    pub async fn send_async_data(&mut self, data: &[u8]) -> Result<TransmitResult> {
+        let frames = trx_command::async_data(self.seqnbr, data)?;
+        debug!("Sending {} bytes of async data", data.len());
+        let mut result = TransmitResult::Ack;
+        for mut frame in frames {
+            frame[3] = self.next_seqnbr();
+            result = self.transmit_reserved(frame).await?;
+        }
+        Ok(result)
+    }
+}
+
+impl Receiver {
+    /// Subscribes to the protocol messages from the device.
+    ///
+    /// Every subscriber receives all messages received after subscribing,
+    /// independent of `read_message` and other subscribers. A subscriber
+    /// that falls behind misses messages and gets a `Lagged` error.
+    pub fn subscribe(&self) -> broadcast::Receiver<ReceivedMessage> {
+        self.broadcast_tx.subscribe()
+    }
+
+    /// Subscribes to the protocol messages selected by `filter`, as `subscribe`.
+    ///
+    /// The messages are filtered in the serial port task, so the other messages
+    /// don't fill the subscriber's queue.
+    pub fn subscribe_filtered(&self, filter: Filter) -> broadcast::Receiver<ReceivedMessage> {
+        self.filtered.subscribe(filter)
+    }
+
+    /// Subscribes to every raw frame sent to and received from the device,
+    /// including the ones that couldn't be parsed. Frames start with the length byte.
+    pub fn raw_frames(&self) -> broadcast::Receiver<RawFrame> {
+        self.raw_tx.subscribe()
+    }
+
+    /// Returns a stream of the protocol messages from the device.
+    ///
+    /// The stream ends when the connection to the device is closed.
+    pub fn messages(&mut self) -> Messages<'_> {
+        Messages {
+            protocol_msg_rx: &mut self.protocol_msg_rx,
+        }
+    }
+
+    /// This function will wait for protocol messages from the device.
+    ///
+    /// Messages are queued until read, when the queue is full new messages are dropped.
+    /// If a read timeout is set with `RFXtrx433Builder::read_timeout`, `TRXError::Timeout`
+    /// is returned when no message is received in time.
+    pub async fn read_message(&mut self) -> Result<ReceivedMessage> {
+        match self.read_timeout {
+            Some(timeout) => self.read_message_timeout(timeout).await,
+            None => self.recv().await,
+        }
+    }
+
+    /// Waits for a protocol message from the device, or returns `TRXError::Timeout`
+    /// if none is received within `timeout`.
+    pub async fn read_message_timeout(&mut self, timeout: Duration) -> Result<ReceivedMessage> {
+        runtime::timeout(timeout, self.recv()).await?
+    }
+
+    /// Drops the messages already queued
+    fn discard_queued(&mut self) {
+        while let Ok(msg) = self.protocol_msg_rx.try_recv() {
+            debug!("Discarding {:?}", msg);
+        }
+    }
+
+    async fn recv(&mut self) -> Result<ReceivedMessage> {
         let cmd = self
             .protocol_msg_rx
             .recv()
@@ -310,6 +1992,91 @@ impl RFXtrx433 {
     }
 }
 
+/// A protocol message with the time it was received and the packet it was parsed from
+#[derive(Clone, Debug)]
+pub struct ReceivedMessage {
+    /// The message
+    pub message: trx_command::ProtocolMessage,
+    /// Header of the packet
+    pub header: trx_command::PacketHeader,
+    /// The packet as received, starting with the length byte
+    pub raw: Vec<u8>,
+    /// Time the message was read from the device
+    pub timestamp: SystemTime,
+    /// Monotonic time the message was read from the device, for measuring
+    /// intervals unaffected by clock changes
+    pub instant: Instant,
+    /// Unit of `temperature`, see `RFXtrx433Builder::temperature_unit`
+    pub temperature_unit: TemperatureUnit,
+}
+
+impl ReceivedMessage {
+    /// Time since the message was received
+    pub fn age(&self) -> Duration {
+        self.instant.elapsed()
+    }
+
+    /// Packet type
+    pub fn packet_type(&self) -> trx_command::PacketType {
+        self.header.packet_type()
+    }
+
+    /// Sub type, its meaning depends on the packet type
+    pub fn sub_type(&self) -> u8 {
+        self.header.sub_type()
+    }
+
+    /// Sequence number of the packet
+    pub fn seqnbr(&self) -> u8 {
+        self.header.seqnbr()
+    }
+
+    /// Returns the id of the device that sent the message, see `ProtocolMessage::device_id`
+    pub fn device_id(&self) -> Option<DeviceId> {
+        self.message.device_id()
+    }
+
+    /// Temperature of sensor messages in `temperature_unit`, see
+    /// `ProtocolMessage::temperature`
+    pub fn temperature(&self) -> Option<f32> {
+        self.message
+            .temperature()
+            .map(|temp| self.temperature_unit.convert(temp))
+    }
+
+    /// Returns the message, dropping the packet and timestamps
+    pub fn into_message(self) -> trx_command::ProtocolMessage {
+        self.message
+    }
+}
+
+/// Stream of protocol messages, see `Receiver::messages`
+pub struct Messages<'a> {
+    protocol_msg_rx: &'a mut BoundedReceiver<ReceivedMessage>,
+}
+
+impl futures_core::Stream for Messages<'_> {
+    type Item = Result<ReceivedMessage>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.protocol_msg_rx.poll_recv(cx).map(|msg| msg.map(Ok))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Health of the device, reported by the watchdog
+pub enum HealthEvent {
+    /// The device answered the status request
+    Healthy,
+    /// The device didn't answer the status request in time
+    Unresponsive,
+    /// The connection to the device is closed
+    Disconnected,
+}
+
 #[derive(Debug)]
 /// Information about the hardware
 pub struct RFXtrx433Info {
@@ -317,4 +2084,374 @@ pub struct RFXtrx433Info {
     pub frequency: trx_command::Frequency,
     /// Currently enabled protocols
     pub enabled_protocols: trx_command::EnabledProtocols,
+    /// Firmware version
+    pub fw_version: u8,
+    /// Firmware type, not reported by older firmware
+    pub fw_type: Option<trx_command::FWType>,
+    /// Hardware major version
+    pub hw_version_major: u8,
+    /// Hardware minor version
+    pub hw_version_minor: u8,
+    /// Output power setting
+    pub output_power: u8,
+    /// Device name, not reported by older firmware
+    pub device_name: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn get_status_over_transport() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        let status = tokio::spawn(async move { rfx.get_status().await });
+
+        let mut request = [0u8; 14];
+        device.read_exact(&mut request).await.unwrap();
+        assert_eq!(0x02, request[4]);
+        device
+            .write_all(&[
+                0x0D, 0x01, 0x00, request[3], 0x02, 0x53, 0x0C, 0x08, 0x00, 0x27, 0x00, 0x01, 0x03,
+                0x1C,
+            ])
+            .await
+            .unwrap();
+
+        let status = status.await.unwrap().unwrap();
+        assert!(matches!(status.frequency, Frequency::TrxType43392));
+        assert_eq!(0x0C, status.fw_version);
+    }
+
+    #[tokio::test]
+    async fn initialize_sequence() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        let device = tokio::spawn(async move {
+            let mut request = [0u8; 14];
+            device.read_exact(&mut request).await.unwrap();
+            assert_eq!(0x00, request[4]);
+            // Received while resetting
+            device
+                .write_all(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70])
+                .await
+                .unwrap();
+            // Noise lasting past the reset delay, ending within a frame
+            for _ in 0..120 {
+                device.write_all(&[0x20]).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+
+            device.read_exact(&mut request).await.unwrap();
+            assert_eq!(0x02, request[4]);
+            device
+                .write_all(&[
+                    0x0D, 0x01, 0x00, request[3], 0x02, 0x53, 0x0C, 0x08, 0x00, 0x27, 0x00, 0x01,
+                    0x03, 0x1C,
+                ])
+                .await
+                .unwrap();
+
+            device.read_exact(&mut request).await.unwrap();
+            assert_eq!(0x07, request[4]);
+            let mut response = vec![0x14, 0x01, 0x07, request[3], 0x07];
+            response.extend_from_slice(COPYRIGHT.as_bytes());
+            device.write_all(&response).await.unwrap();
+            device
+        });
+
+        let info = rfx.initialize(InitOptions::default()).await.unwrap();
+        assert_eq!(0x0C, info.fw_version);
+        let _device = device.await.unwrap();
+        let res = rfx.read_message_timeout(Duration::from_millis(10)).await;
+        assert!(matches!(res, Err(TRXError::Timeout)));
+    }
+
+    #[test]
+    fn candidate_ports() {
+        assert!(is_candidate(0x0403, 0x6001, None));
+        assert!(is_candidate(0x0403, 0x6015, Some("FT230X Basic UART")));
+        assert!(is_candidate(0x1234, 0x0001, Some("RFXtrx433XL")));
+        assert!(!is_candidate(0x0403, 0x6010, Some("Dual RS232")));
+        assert!(!is_candidate(0x10c4, 0xea60, Some("CP2102")));
+    }
+
+    #[tokio::test]
+    async fn raw_frames_tap() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+        let mut raw_frames = rfx.raw_frames();
+
+        // Unknown packet type, not parsed
+        device.write_all(&[0x03, 0xEE, 0x00, 0x00]).await.unwrap();
+        let frame = raw_frames.recv().await.unwrap();
+        assert_eq!(Direction::Received, frame.direction);
+        assert_eq!(vec![0x03, 0xEE, 0x00, 0x00], frame.data);
+    }
+
+    #[tokio::test]
+    async fn received_message_envelope() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        let before = SystemTime::now();
+        device
+            .write_all(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70])
+            .await
+            .unwrap();
+        let msg = rfx.read_message().await.unwrap();
+        assert!(matches!(msg.message, ProtocolMessage::Lighting1(_)));
+        assert_eq!(PacketType::Lighting1, msg.packet_type());
+        assert_eq!(0x01, msg.sub_type());
+        assert_eq!(
+            vec![0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70],
+            msg.raw
+        );
+        assert!(msg.timestamp >= before && msg.timestamp <= SystemTime::now());
+        assert!(msg.age() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn pairing() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        tokio::spawn(async move {
+            let mut request = [0u8; 12];
+            while device.read_exact(&mut request).await.is_ok() {
+                assert_eq!(0x11, request[1]);
+                // On
+                assert_eq!(0x01, request[9]);
+                device
+                    .write_all(&[0x04, 0x02, 0x01, request[3], 0x00])
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let mut progress = Vec::new();
+        let options = PairingOptions {
+            window: Duration::from_millis(30),
+            interval: Duration::from_millis(10),
+        };
+        let attempts = rfx
+            .pair_lighting2(
+                Lighting2SubType::Ac,
+                RemoteId::from(0x0123_4567),
+                UnitCode::new(3).unwrap(),
+                options,
+                |p| progress.push(p),
+            )
+            .await
+            .unwrap();
+        assert!(attempts >= 2);
+        assert_eq!(attempts as usize, progress.len());
+        assert_eq!(attempts, progress.last().unwrap().attempt);
+        assert!(progress.last().unwrap().remaining < options.interval);
+    }
+
+    #[tokio::test]
+    async fn repeated_transmit() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        let device = tokio::spawn(async move {
+            let mut seqnbrs = Vec::new();
+            // Not acknowledged, then acknowledged
+            for ack in [0x02, 0x00, 0x00] {
+                let mut request = [0u8; 8];
+                device.read_exact(&mut request).await.unwrap();
+                assert_eq!(0x10, request[1]);
+                seqnbrs.push(request[3]);
+                device
+                    .write_all(&[0x04, 0x02, 0x01, request[3], ack])
+                    .await
+                    .unwrap();
+            }
+            seqnbrs
+        });
+
+        let policy = RepeatPolicy {
+            count: 3,
+            delay: Duration::from_millis(1),
+        };
+        let result = rfx
+            .with_repeat(policy)
+            .send_lighting1(
+                Lighting1SubType::Arc,
+                HouseCode::new('A').unwrap(),
+                UnitCode::new(1).unwrap(),
+                Lighting1Command::On,
+            )
+            .await
+            .unwrap();
+        assert_eq!(TransmitResult::Ack, result);
+        assert_eq!(RepeatPolicy::default(), rfx.repeat());
+        assert_eq!(vec![0, 1, 2], device.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn watchdog() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+        let mut health = rfx.start_watchdog(Duration::from_millis(40), Duration::from_millis(20));
+
+        // Other frames don't answer the status request
+        let mut request = [0u8; 14];
+        device.read_exact(&mut request).await.unwrap();
+        assert_eq!(INTERNAL_SEQNBR, request[3]);
+        device
+            .write_all(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70])
+            .await
+            .unwrap();
+        assert_eq!(Some(HealthEvent::Unresponsive), health.recv().await);
+
+        device.read_exact(&mut request).await.unwrap();
+        device
+            .write_all(&[
+                0x0D, 0x01, 0x00, request[3], 0x02, 0x53, 0x0C, 0x08, 0x00, 0x27, 0x00, 0x01, 0x03,
+                0x1C,
+            ])
+            .await
+            .unwrap();
+        assert_eq!(Some(HealthEvent::Healthy), health.recv().await);
+
+        // The watchdog doesn't keep the connection open
+        drop(rfx);
+        assert_eq!(Some(HealthEvent::Disconnected), health.recv().await);
+    }
+
+    #[tokio::test]
+    async fn empty_queue_rejected() {
+        let (transport, _device) = tokio::io::duplex(256);
+        let res = RFXtrx433::builder()
+            .queue_len(0)
+            .open_transport(transport)
+            .await;
+        assert!(matches!(res, Err(TRXError::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn async_port_acknowledged() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        let device = tokio::spawn(async move {
+            // Not acknowledged, then acknowledged
+            for ack in [0x02, 0x00] {
+                let len = device.read_u8().await.unwrap();
+                let mut request = vec![0u8; len as usize];
+                device.read_exact(&mut request).await.unwrap();
+                device
+                    .write_all(&[0x04, 0x02, 0x01, request[2], ack])
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let config = AsyncPort {
+            command: AsyncPortCommand::ReceiveRaw,
+            baud_rate: AsyncBaudRate::Baud9600,
+            parity: AsyncParity::None,
+            data_bits: 8,
+            stop_bits: 1,
+            inverted: false,
+        };
+        let res = rfx.configure_async_port(config).await;
+        assert!(matches!(res, Err(TRXError::TransmitFailed(_))));
+        let res = rfx.send_async_data(b"hello").await.unwrap();
+        assert_eq!(TransmitResult::Ack, res);
+        device.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn raw_pulses_skip_internal_seqnbr() {
+        let (transport, mut device) = tokio::io::duplex(1024);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+        rfx.sender.seqnbr = 0xFD;
+
+        let device = tokio::spawn(async move {
+            let mut seqnbrs = Vec::new();
+            for _ in 0..3 {
+                let len = device.read_u8().await.unwrap();
+                let mut request = vec![0u8; len as usize];
+                device.read_exact(&mut request).await.unwrap();
+                seqnbrs.push(request[2]);
+            }
+            // Only the last frame is answered
+            device
+                .write_all(&[0x04, 0x02, 0x01, seqnbrs[2], 0x00])
+                .await
+                .unwrap();
+            seqnbrs
+        });
+
+        let res = rfx.send_raw_pulses(&[100; 300], 1).await.unwrap();
+        assert_eq!(TransmitResult::Ack, res);
+        assert_eq!(vec![0xFD, 0xFE, 0x00], device.await.unwrap());
+        assert_eq!(0x01, rfx.sender.seqnbr);
+    }
+
+    #[tokio::test]
+    async fn async_data_skips_internal_seqnbr() {
+        let (transport, mut device) = tokio::io::duplex(1024);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+        rfx.sender.seqnbr = 0xFE;
+
+        let device = tokio::spawn(async move {
+            let mut seqnbrs = Vec::new();
+            for _ in 0..3 {
+                let len = device.read_u8().await.unwrap();
+                let mut request = vec![0u8; len as usize];
+                device.read_exact(&mut request).await.unwrap();
+                seqnbrs.push(request[2]);
+                device
+                    .write_all(&[0x04, 0x02, 0x01, request[2], 0x00])
+                    .await
+                    .unwrap();
+            }
+            seqnbrs
+        });
+
+        let res = rfx.send_async_data(&[0; 600]).await.unwrap();
+        assert_eq!(TransmitResult::Ack, res);
+        assert_eq!(vec![0xFE, 0x00, 0x01], device.await.unwrap());
+        assert_eq!(0x02, rfx.sender.seqnbr);
+    }
+
+    #[tokio::test]
+    async fn lighting6_counters() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+        let id = RemoteId::from(0x1234);
+        rfx.set_lighting6_counters(id, 4, 0xFF).unwrap();
+        assert!(rfx.set_lighting6_counters(id, 5, 0).is_err());
+
+        let device = tokio::spawn(async move {
+            let len = device.read_u8().await.unwrap();
+            let mut request = vec![0u8; len as usize];
+            device.read_exact(&mut request).await.unwrap();
+            device
+                .write_all(&[0x04, 0x02, 0x01, request[2], 0x00])
+                .await
+                .unwrap();
+            (request[8], request[9])
+        });
+
+        rfx.send_lighting6(
+            Lighting6SubType::Blyss,
+            id,
+            HouseCode::new('A').unwrap(),
+            UnitCode::new(1).unwrap(),
+            Lighting6Command::On,
+        )
+        .await
+        .unwrap();
+        assert_eq!((4, 0xFF), device.await.unwrap());
+        assert_eq!((0, 0x00), rfx.lighting6_counters(id));
+        assert_eq!((0, 0), rfx.lighting6_counters(RemoteId::from(0x5678)));
+    }
 }
@@ -0,0 +1,132 @@
+//! In-memory device for testing applications without hardware
+use crate::{read_message, BoxedTransport, RFXtrx433, Result, TRXError};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::AsyncWriteExt,
+    select,
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+/// Scripted stand-in for a RFXtrx433 device.
+///
+/// Every frame sent by the library is recorded, and answered with the next queued
+/// response, if any. Frames can also be injected as if received over the air.
+/// Frames are as on the wire, starting with the length byte.
+pub struct MockRfxtrx {
+    inject_tx: UnboundedSender<Vec<u8>>,
+    responses: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    sent: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl MockRfxtrx {
+    /// Creates a mock device and a `RFXtrx433` connected to it
+    pub async fn connect() -> Result<(RFXtrx433, MockRfxtrx)> {
+        let (transport, device) = tokio::io::duplex(1024);
+        let (inject_tx, inject_rx) = unbounded_channel();
+        let mock = MockRfxtrx {
+            inject_tx,
+            responses: Default::default(),
+            sent: Default::default(),
+        };
+        crate::runtime::spawn(device_task(
+            Box::new(device),
+            inject_rx,
+            mock.responses.clone(),
+            mock.sent.clone(),
+        ));
+        let rfx = RFXtrx433::new_from_transport(transport).await?;
+        Ok((rfx, mock))
+    }
+
+    /// Queues the response to the next frame sent by the library.
+    /// The sequence number is set to the one of the sent frame.
+    pub fn queue_response(&self, frame: &[u8]) {
+        self.responses.lock().unwrap().push_back(frame.to_vec());
+    }
+
+    /// Queues a transmitter ACK as the response to the next frame
+    pub fn queue_ack(&self) {
+        self.queue_response(&[0x04, 0x02, 0x01, 0x00, 0x00]);
+    }
+
+    /// Sends a frame to the library, as if received by the device
+    pub fn inject(&self, frame: &[u8]) -> Result<()> {
+        self.inject_tx
+            .send(frame.to_vec())
+            .map_err(|e| TRXError::TokioSendError(format!("{}", e)))
+    }
+
+    /// Returns the frames sent by the library so far
+    pub fn sent_frames(&self) -> Vec<Vec<u8>> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+async fn device_task(
+    mut device: BoxedTransport,
+    mut inject_rx: UnboundedReceiver<Vec<u8>>,
+    responses: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    sent: Arc<Mutex<Vec<Vec<u8>>>>,
+) -> Result<()> {
+    loop {
+        select! {
+            frame = inject_rx.recv() => match frame {
+                None => return Ok(()),
+                Some(frame) => device.write_all(&frame).await?,
+            },
+            msg = read_message(&mut device) => {
+                let msg = match msg? {
+                    Some(msg) => msg,
+                    None => continue,
+                };
+                let mut frame = vec![msg.len() as u8];
+                frame.extend_from_slice(&msg);
+                sent.lock().unwrap().push(frame);
+
+                let response = responses.lock().unwrap().pop_front();
+                if let Some(mut response) = response {
+                    if let (Some(seqnbr), true) = (msg.get(2), response.len() > 3) {
+                        response[3] = *seqnbr;
+                    }
+                    device.write_all(&response).await?;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        HouseCode, Lighting1Command, Lighting1SubType, ProtocolMessage, TransmitResult, UnitCode,
+    };
+
+    #[tokio::test]
+    async fn mock_transmit_and_receive() {
+        let (mut rfx, mock) = MockRfxtrx::connect().await.unwrap();
+
+        mock.queue_ack();
+        let result = rfx
+            .send_lighting1(
+                Lighting1SubType::Arc,
+                HouseCode::new('B').unwrap(),
+                UnitCode::new(5).unwrap(),
+                Lighting1Command::On,
+            )
+            .await
+            .unwrap();
+        assert_eq!(TransmitResult::Ack, result);
+        let sent = mock.sent_frames();
+        assert_eq!(1, sent.len());
+        assert_eq!(&[0x07, 0x10, 0x01], &sent[0][..3]);
+
+        mock.inject(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70])
+            .unwrap();
+        let msg = rfx.read_message().await.unwrap();
+        assert!(matches!(msg.message, ProtocolMessage::Lighting1(_)));
+    }
+}
@@ -89,3 +89,304 @@ bitflags! {
         const FUNKBUS = 1<<7;
     }
 }
+
+bitflags! {
+    /// 868 MHz protocols
+    pub struct Protocols868_1:u8 {
+        /// Alecto ACH2010
+        const ALECTO = 1<<0;
+        /// Alecto WS5500
+        const ALECTO5500 = 1<<1;
+        /// LaCrosse
+        const LACROSSE = 1<<2;
+        /// Davis EU
+        const DAVISEU = 1<<3;
+        /// Davis US
+        const DAVISUS = 1<<4;
+        /// Davis AU
+        const DAVISAU = 1<<5;
+        /// Reserved for future use
+        const MSG3_RESERVED_6 = 1<<6;
+        /// Undecoded messages
+        const UNDECODED = 1<<7;
+    }
+}
+
+bitflags! {
+    /// 868 MHz protocols
+    pub struct Protocols868_2:u8 {
+        /// Reserved for future use
+        const MSG4_RESERVED_0 = 1<<0;
+        /// Reserved for future use
+        const MSG4_RESERVED_1 = 1<<1;
+        /// Reserved for future use
+        const MSG4_RESERVED_2 = 1<<2;
+        /// Reserved for future use
+        const MSG4_RESERVED_3 = 1<<3;
+        /// Proguard
+        const PROGUARD = 1<<4;
+        /// FS20
+        const FS20 = 1<<5;
+        /// AD LightwaveRF
+        const LWRF = 1<<6;
+        /// Edisio
+        const EDISIO = 1<<7;
+    }
+}
+
+bitflags! {
+    /// 868 MHz protocols
+    pub struct Protocols868_3:u8 {
+        /// Itho CVE RFT
+        const ITHO = 1<<0;
+        /// Itho CVE ECO RFT
+        const ITHOCVE = 1<<1;
+        /// Itho HRU400
+        const ITHOHRU400 = 1<<2;
+        /// Honeywell chime
+        const HONCHIME = 1<<3;
+        /// Reserved for future use
+        const MSG5_RESERVED_4 = 1<<4;
+        /// Reserved for future use
+        const MSG5_RESERVED_5 = 1<<5;
+        /// Reserved for future use
+        const MSG5_RESERVED_6 = 1<<6;
+        /// Reserved for future use
+        const MSG5_RESERVED_7 = 1<<7;
+    }
+}
+
+bitflags! {
+    /// 868 MHz protocols
+    pub struct Protocols868_4:u8 {
+        /// Keeloq
+        const KEELOQ = 1<<0;
+        /// Visonic PowerCode
+        const VISONIC = 1<<1;
+        /// Proove
+        const PROOVE = 1<<2;
+        /// Meiantech
+        const MEIANTECH = 1<<3;
+        /// Reserved for future use
+        const MSG6_RESERVED_4 = 1<<4;
+        /// Reserved for future use
+        const MSG6_RESERVED_5 = 1<<5;
+        /// Reserved for future use
+        const MSG6_RESERVED_6 = 1<<6;
+        /// Reserved for future use
+        const MSG6_RESERVED_7 = 1<<7;
+    }
+}
+
+/// Curated protocol combinations, so the RFXCOM SDK bit tables don't need to be studied
+/// to get started. Presets can be combined with `|`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModePreset {
+    /// Protocols in the first mode byte
+    pub protos_1: Protocols1,
+    /// Protocols in the second mode byte
+    pub protos_2: Protocols2,
+    /// Protocols in the third mode byte
+    pub protos_3: Protocols3,
+    /// Protocols in the fourth mode byte
+    pub protos_4: Protocols4,
+}
+
+impl ModePreset {
+    /// Temperature, humidity, rain and wind sensors
+    pub fn weather_sensors() -> Self {
+        ModePreset {
+            protos_1: Protocols1::RUBICSON | Protocols1::FINEOFFSET | Protocols1::IMAGINTRONIX,
+            protos_2: Protocols2::HIDEKI | Protocols2::LACROSSE,
+            protos_3: Protocols3::OREGON,
+            protos_4: Protocols4::empty(),
+        }
+    }
+
+    /// Switches, dimmers and remotes for lighting
+    pub fn lighting() -> Self {
+        ModePreset {
+            protos_1: Protocols1::AE | Protocols1::LIGHTING4 | Protocols1::RSL,
+            protos_2: Protocols2::LWRF,
+            protos_3: Protocols3::X10 | Protocols3::ARC | Protocols3::AC | Protocols3::HEEU,
+            protos_4: Protocols4::HC,
+        }
+    }
+
+    /// Alarm sensors and remotes
+    pub fn security() -> Self {
+        ModePreset {
+            protos_1: Protocols1::empty(),
+            protos_2: Protocols2::empty(),
+            protos_3: Protocols3::X10 | Protocols3::MEIANTECH | Protocols3::VISONIC,
+            protos_4: Protocols4::KEELOQ,
+        }
+    }
+
+    /// Blinds and awning motors
+    pub fn blinds() -> Self {
+        ModePreset {
+            protos_1: Protocols1::empty(),
+            protos_2: Protocols2::BLINDST0 | Protocols2::BLINDST1,
+            protos_3: Protocols3::empty(),
+            protos_4: Protocols4::empty(),
+        }
+    }
+}
+
+impl std::ops::BitOr for ModePreset {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        ModePreset {
+            protos_1: self.protos_1 | rhs.protos_1,
+            protos_2: self.protos_2 | rhs.protos_2,
+            protos_3: self.protos_3 | rhs.protos_3,
+            protos_4: self.protos_4 | rhs.protos_4,
+        }
+    }
+}
+
+/// The protocols are serialized as arrays of their names, e.g. `["OREGON", "X10"]`,
+/// so they stay readable and don't depend on the bit layout
+#[cfg(feature = "serde")]
+mod serde_names {
+    use super::*;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    fn serialize<S: Serializer>(
+        bits: u8,
+        names: &[(&str, u8)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let flags: Vec<&str> = names
+            .iter()
+            .filter(|(_, bit)| (bits & bit) != 0)
+            .map(|(name, _)| *name)
+            .collect();
+        flags.serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        names: &[(&str, u8)],
+        deserializer: D,
+    ) -> Result<u8, D::Error> {
+        let flags = Vec::<String>::deserialize(deserializer)?;
+        flags.iter().try_fold(0, |bits, flag| {
+            names
+                .iter()
+                .find(|(name, _)| *name == flag.as_str())
+                .map(|(_, bit)| bits | bit)
+                .ok_or_else(|| {
+                    <D::Error as de::Error>::custom(format!("unknown protocol {}", flag))
+                })
+        })
+    }
+
+    macro_rules! serde_by_name {
+        ($flags:ident { $($flag:ident),* $(,)? }) => {
+            impl Serialize for $flags {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let names = [$((stringify!($flag), $flags::$flag.bits())),*];
+                    serialize(self.bits(), &names, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $flags {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let names = [$((stringify!($flag), $flags::$flag.bits())),*];
+                    deserialize(&names, deserializer).map($flags::from_bits_truncate)
+                }
+            }
+        };
+    }
+
+    serde_by_name!(Protocols1 {
+        AE,
+        RUBICSON,
+        FINEOFFSET,
+        LIGHTING4,
+        RSL,
+        SX,
+        IMAGINTRONIX,
+        UNDECODED,
+    });
+
+    serde_by_name!(Protocols2 {
+        MERTIK,
+        LWRF,
+        HIDEKI,
+        LACROSSE,
+        LEGRAND,
+        MSG4_RESERVED_55,
+        BLINDST0,
+        BLINDST1,
+    });
+
+    serde_by_name!(Protocols3 {
+        X10,
+        ARC,
+        AC,
+        HEEU,
+        MEIANTECH,
+        OREGON,
+        ATI,
+        VISONIC,
+    });
+
+    serde_by_name!(Protocols4 {
+        KEELOQ,
+        HC,
+        MSG6_RESERVED_2,
+        MSG6_RESERVED_3,
+        MSG6_RESERVED_4,
+        MSG6_RESERVED_5,
+        MCZ,
+        FUNKBUS,
+    });
+
+    serde_by_name!(Protocols868_1 {
+        ALECTO,
+        ALECTO5500,
+        LACROSSE,
+        DAVISEU,
+        DAVISUS,
+        DAVISAU,
+        MSG3_RESERVED_6,
+        UNDECODED,
+    });
+
+    serde_by_name!(Protocols868_2 {
+        MSG4_RESERVED_0,
+        MSG4_RESERVED_1,
+        MSG4_RESERVED_2,
+        MSG4_RESERVED_3,
+        PROGUARD,
+        FS20,
+        LWRF,
+        EDISIO,
+    });
+
+    serde_by_name!(Protocols868_3 {
+        ITHO,
+        ITHOCVE,
+        ITHOHRU400,
+        HONCHIME,
+        MSG5_RESERVED_4,
+        MSG5_RESERVED_5,
+        MSG5_RESERVED_6,
+        MSG5_RESERVED_7,
+    });
+
+    serde_by_name!(Protocols868_4 {
+        KEELOQ,
+        VISONIC,
+        PROOVE,
+        MEIANTECH,
+        MSG6_RESERVED_4,
+        MSG6_RESERVED_5,
+        MSG6_RESERVED_6,
+        MSG6_RESERVED_7,
+    });
+}
@@ -0,0 +1,176 @@
+//! Recording of raw frames to a file, and replay of such recordings
+use crate::{Result, TRXError};
+use log::warn;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+    time::SystemTime,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// Direction of a raw frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// Sent to the device
+    Sent,
+    /// Received from the device
+    Received,
+}
+
+/// Frame as sent or received on the wire, see `Receiver::raw_frames`
+#[derive(Clone, Debug)]
+pub struct RawFrame {
+    /// Sent or received
+    pub direction: Direction,
+    /// Time the frame was sent or received
+    pub timestamp: SystemTime,
+    /// The frame, starting with the length byte
+    pub data: Vec<u8>,
+}
+
+impl Direction {
+    fn tag(self) -> &'static str {
+        match self {
+            Direction::Sent => "TX",
+            Direction::Received => "RX",
+        }
+    }
+}
+
+/// Writes every frame on its own line: milliseconds since the UNIX epoch,
+/// TX or RX, and the frame in hex, starting with the length byte.
+///
+/// Every frame is written with a single write and flushed, so the recording
+/// is complete up to the last frame even if the process is killed.
+pub(crate) struct Recorder {
+    file: BufWriter<File>,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path) -> Result<Self> {
+        Ok(Recorder {
+            file: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    pub(crate) fn record(&mut self, direction: Direction, timestamp: SystemTime, frame: &[u8]) {
+        let millis = timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let hex: String = frame.iter().map(|b| format!("{:02X}", b)).collect();
+        // A broken recording shouldn't stop the device
+        if let Err(e) = writeln!(self.file, "{} {} {}", millis, direction.tag(), hex)
+            .and_then(|_| self.file.flush())
+        {
+            warn!("Failed to record frame: {}", e);
+        }
+    }
+}
+
+/// Transport playing back the received frames of a recording, see
+/// `RFXtrx433Builder::record`. Frames sent to it are discarded.
+///
+/// The frames are replayed as fast as they are read, the end of the recording
+/// closes the connection. Frames beyond the message queue length are dropped
+/// unless they are read while replaying.
+pub struct ReplayTransport {
+    data: std::io::Cursor<Vec<u8>>,
+}
+
+impl ReplayTransport {
+    /// Reads a recording
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let mut data = Vec::new();
+        for (n, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(_), Some("RX"), Some(hex)) => {
+                    data.extend(parse_hex(hex).ok_or_else(|| {
+                        TRXError::InvalidArgument(format!("Invalid frame on line {}", n + 1))
+                    })?)
+                }
+                (Some(_), Some("TX"), Some(_)) => {}
+                _ if line.trim().is_empty() => {}
+                _ => {
+                    return Err(TRXError::InvalidArgument(format!(
+                        "Invalid recording line {}",
+                        n + 1
+                    )))
+                }
+            }
+        }
+        Ok(ReplayTransport {
+            data: std::io::Cursor::new(data),
+        })
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|byte| match byte {
+            [_, _] => u8::from_str_radix(std::str::from_utf8(byte).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+impl AsyncRead for ReplayTransport {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.data).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ReplayTransport {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ProtocolMessage, RFXtrx433};
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let path = std::env::temp_dir().join(format!("rfxtrx433-{}.rec", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder.record(Direction::Sent, SystemTime::now(), &[0x01, 0x02]);
+        recorder.record(
+            Direction::Received,
+            SystemTime::now(),
+            &[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70],
+        );
+        // Written without closing the recording
+        assert_eq!(2, std::fs::read_to_string(&path).unwrap().lines().count());
+        drop(recorder);
+
+        let transport = ReplayTransport::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+        let msg = rfx.read_message().await.unwrap();
+        assert!(matches!(msg.message, ProtocolMessage::Lighting1(_)));
+    }
+}
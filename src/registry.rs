@@ -0,0 +1,182 @@
+//! Tracks the devices heard by the receiver
+use crate::{trx_command::ProtocolMessage, DeviceId, ReceivedMessage, Rssi};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// Number of signal strengths kept per device by default
+const RSSI_HISTORY_LEN: usize = 16;
+
+/// A device heard by the receiver, see `DeviceRegistry`
+#[derive(Clone, Debug)]
+pub struct DeviceRecord {
+    /// Id of the device, with its packet type and model
+    pub id: DeviceId,
+    /// Time the device was first heard
+    pub first_seen: SystemTime,
+    /// Time the device was last heard
+    pub last_seen: SystemTime,
+    /// Number of messages received from the device
+    pub message_count: u64,
+    /// The last message received from the device
+    pub last_message: ProtocolMessage,
+    /// Signal strength of the latest messages with the time they were received,
+    /// oldest first
+    pub rssi_history: VecDeque<(SystemTime, Rssi)>,
+}
+
+impl DeviceRecord {
+    /// Average signal strength level of the latest messages, 0 - 15
+    pub fn average_rssi(&self) -> Option<f32> {
+        if self.rssi_history.is_empty() {
+            return None;
+        }
+        let sum: u32 = self
+            .rssi_history
+            .iter()
+            .map(|(_, rssi)| rssi.value() as u32)
+            .sum();
+        Some(sum as f32 / self.rssi_history.len() as f32)
+    }
+}
+
+/// Records every device heard, with its last message and signal strength history.
+///
+/// Pass a clone to `RFXtrx433Builder::device_registry` to have it updated with all
+/// received messages, or feed it messages with `record`. Clones share the same devices.
+#[derive(Clone, Debug)]
+pub struct DeviceRegistry {
+    devices: Arc<Mutex<HashMap<DeviceId, DeviceRecord>>>,
+    rssi_history_len: usize,
+}
+
+impl Default for DeviceRegistry {
+    fn default() -> Self {
+        DeviceRegistry::new()
+    }
+}
+
+impl DeviceRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        DeviceRegistry::with_rssi_history(RSSI_HISTORY_LEN)
+    }
+
+    /// Creates an empty registry keeping the signal strength of the last `len`
+    /// messages per device, the default is 16
+    pub fn with_rssi_history(len: usize) -> Self {
+        DeviceRegistry {
+            devices: Arc::new(Mutex::new(HashMap::new())),
+            rssi_history_len: len,
+        }
+    }
+
+    /// Records a received message. Messages without a device id are ignored.
+    pub fn record(&self, msg: &ReceivedMessage) {
+        let id = match msg.device_id() {
+            Some(id) => id,
+            None => return,
+        };
+        let mut devices = self.lock();
+        let record = devices.entry(id).or_insert_with(|| DeviceRecord {
+            id,
+            first_seen: msg.timestamp,
+            last_seen: msg.timestamp,
+            message_count: 0,
+            last_message: msg.message.clone(),
+            rssi_history: VecDeque::with_capacity(self.rssi_history_len),
+        });
+        record.last_seen = msg.timestamp;
+        record.message_count += 1;
+        record.last_message = msg.message.clone();
+        if let Some(rssi) = msg.message.rssi() {
+            if record.rssi_history.len() == self.rssi_history_len {
+                record.rssi_history.pop_front();
+            }
+            if self.rssi_history_len > 0 {
+                record.rssi_history.push_back((msg.timestamp, rssi));
+            }
+        }
+    }
+
+    /// Returns the device with `id`
+    pub fn get(&self, id: DeviceId) -> Option<DeviceRecord> {
+        self.lock().get(&id).cloned()
+    }
+
+    /// Returns all devices heard, ordered by id
+    pub fn devices(&self) -> Vec<DeviceRecord> {
+        let mut devices: Vec<_> = self.lock().values().cloned().collect();
+        devices.sort_by_key(|device| device.id);
+        devices
+    }
+
+    /// Returns the devices not heard for `max_age`, e.g. sensors with empty batteries
+    pub fn stale(&self, max_age: Duration) -> Vec<DeviceRecord> {
+        let now = SystemTime::now();
+        self.devices()
+            .into_iter()
+            .filter(|device| {
+                now.duration_since(device.last_seen)
+                    .is_ok_and(|age| age > max_age)
+            })
+            .collect()
+    }
+
+    /// Forgets the device with `id`, returning it
+    pub fn remove(&self, id: DeviceId) -> Option<DeviceRecord> {
+        self.lock().remove(&id)
+    }
+
+    /// Forgets all devices
+    pub fn clear(&self) {
+        self.lock().clear()
+    }
+
+    /// Number of devices heard
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if no devices have been heard
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<DeviceId, DeviceRecord>> {
+        // A panic while recording leaves the map consistent, keep using it
+        self.devices.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::parse_frame;
+
+    #[test]
+    fn registry() {
+        let registry = DeviceRegistry::with_rssi_history(2);
+        for rssi in [0x50, 0x60, 0x70] {
+            let msg = parse_frame(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x05, 0x01, rssi]).unwrap();
+            registry.clone().record(&msg);
+        }
+        let msg = parse_frame(&[0x07, 0x10, 0x01, 0x00, 0x42, 0x06, 0x01, 0x70]).unwrap();
+        registry.record(&msg);
+        // Not from a device
+        let msg = parse_frame(&[0x04, 0x5E, 0x01, 0x00, 0x12]).unwrap();
+        registry.record(&msg);
+
+        assert_eq!(2, registry.len());
+        let b5 = &registry.devices()[0];
+        assert_eq!(3, b5.message_count);
+        assert_eq!(2, b5.rssi_history.len());
+        assert_eq!(Some(6.5), b5.average_rssi());
+        assert!(registry.stale(Duration::from_secs(60)).is_empty());
+
+        registry.remove(b5.id).unwrap();
+        assert_eq!(1, registry.len());
+    }
+}
@@ -0,0 +1,153 @@
+//! Spawning, timers and sockets, the parts of the serial port task tied to an async runtime.
+//! The tokio channels and I/O traits used elsewhere work with any executor.
+//!
+//! With the `async-std` feature these use async-std instead of tokio. Serial ports
+//! are opened with tokio-serial in both cases and need a tokio reactor, async-std
+//! applications can connect over TCP or wrap their own stream in `AsyncStdTransport`.
+#[cfg(not(feature = "async-std"))]
+mod imp {
+    use crate::{BoxedTransport, Result, TRXError};
+    use std::{future::Future, time::Duration};
+
+    pub(crate) type JoinHandle<T> = tokio::task::JoinHandle<T>;
+
+    pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        tokio::spawn(future)
+    }
+
+    /// Waits for a spawned task to finish
+    pub(crate) async fn join<T>(handle: JoinHandle<T>) -> Result<T> {
+        handle
+            .await
+            .map_err(|e| TRXError::TaskFailed(format!("{}", e)))
+    }
+
+    pub(crate) async fn sleep(duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+
+    /// Returns `TRXError::Timeout` if `future` doesn't complete within `duration`
+    pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output> {
+        tokio::time::timeout(duration, future)
+            .await
+            .map_err(|_| TRXError::Timeout)
+    }
+
+    pub(crate) async fn connect_tcp(addr: &str) -> Result<BoxedTransport> {
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        // Commands are small, send them right away
+        stream.set_nodelay(true)?;
+        Ok(Box::new(stream))
+    }
+}
+
+#[cfg(feature = "async-std")]
+mod imp {
+    use super::AsyncStdTransport;
+    use crate::{BoxedTransport, Result, TRXError};
+    use std::{future::Future, time::Duration};
+
+    pub(crate) type JoinHandle<T> = async_std::task::JoinHandle<T>;
+
+    pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        async_std::task::spawn(future)
+    }
+
+    /// Waits for a spawned task to finish, async-std propagates panics
+    pub(crate) async fn join<T>(handle: JoinHandle<T>) -> Result<T> {
+        Ok(handle.await)
+    }
+
+    pub(crate) async fn sleep(duration: Duration) {
+        async_std::task::sleep(duration).await
+    }
+
+    /// Returns `TRXError::Timeout` if `future` doesn't complete within `duration`
+    pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output> {
+        async_std::future::timeout(duration, future)
+            .await
+            .map_err(|_| TRXError::Timeout)
+    }
+
+    pub(crate) async fn connect_tcp(addr: &str) -> Result<BoxedTransport> {
+        let stream = async_std::net::TcpStream::connect(addr).await?;
+        // Commands are small, send them right away
+        stream.set_nodelay(true)?;
+        Ok(Box::new(AsyncStdTransport::new(stream)))
+    }
+}
+
+pub(crate) use imp::*;
+
+/// Adapts an async-std stream, e.g. `async_std::net::TcpStream` or a smol `Async`
+/// wrapper, to a `Transport`.
+#[cfg(feature = "async-std")]
+pub struct AsyncStdTransport<T>(T);
+
+#[cfg(feature = "async-std")]
+impl<T> AsyncStdTransport<T> {
+    /// Wraps `stream`
+    pub fn new(stream: T) -> Self {
+        AsyncStdTransport(stream)
+    }
+
+    /// Returns the wrapped stream
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "async-std")]
+mod compat {
+    use super::AsyncStdTransport;
+    use async_std::io::{Read, Write};
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    impl<T: Read + Unpin> AsyncRead for AsyncStdTransport<T> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            match Pin::new(&mut self.0).poll_read(cx, buf.initialize_unfilled()) {
+                Poll::Ready(Ok(n)) => {
+                    buf.advance(n);
+                    Poll::Ready(Ok(()))
+                }
+                Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    impl<T: Write + Unpin> AsyncWrite for AsyncStdTransport<T> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.0).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.0).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.0).poll_close(cx)
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Commands sent together, e.g. "all blinds down and the lights off"
+use crate::{
+    runtime,
+    trx_command::{
+        CameraCommand, ChimeSubType, HomeConfortCommand, HunterFanCommand, Lighting1Command,
+        Lighting1SubType, Lighting2Command, Lighting2SubType, Lighting3Command, Lighting5SubType,
+        Lighting6Command, Lighting6SubType, RemoteSubType, RfyCommand, RfySubType, Security1Status,
+        Security1SubType, Thermostat1Mode, Thermostat1Status, Thermostat1SubType,
+        Thermostat3Command, Thermostat3SubType, TransmitError, TransmitResult,
+    },
+    HouseCode, RemoteId, Result, Sender, TRXError, UnitCode,
+};
+use log::debug;
+use std::time::Duration;
+
+/// A transmit command, with the arguments of the `Sender` method of the same name
+#[derive(Clone, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum SceneCommand {
+    /// See `Sender::send_lighting1`
+    Lighting1 {
+        sub_type: Lighting1SubType,
+        house_code: HouseCode,
+        unit_code: UnitCode,
+        command: Lighting1Command,
+    },
+    /// See `Sender::send_lighting2`
+    Lighting2 {
+        sub_type: Lighting2SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: Lighting2Command,
+        level: u8,
+    },
+    /// See `Sender::send_lighting3`
+    Lighting3 {
+        system: RemoteId,
+        channels: u16,
+        command: Lighting3Command,
+    },
+    /// See `Sender::send_lighting5`
+    Lighting5 {
+        sub_type: Lighting5SubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: u8,
+        level: u8,
+    },
+    /// See `Sender::send_lighting6`
+    Lighting6 {
+        sub_type: Lighting6SubType,
+        id: RemoteId,
+        group_code: HouseCode,
+        unit_code: UnitCode,
+        command: Lighting6Command,
+    },
+    /// See `Sender::send_chime`
+    Chime {
+        sub_type: ChimeSubType,
+        id: RemoteId,
+        sound: u8,
+    },
+    /// See `Sender::send_rfy`
+    Rfy {
+        sub_type: RfySubType,
+        id: RemoteId,
+        unit_code: UnitCode,
+        command: RfyCommand,
+    },
+    /// See `Sender::send_home_confort`
+    HomeConfort {
+        id: RemoteId,
+        house_code: HouseCode,
+        unit_code: UnitCode,
+        command: HomeConfortCommand,
+    },
+    /// See `Sender::send_hunter_fan`
+    HunterFan {
+        id: [u8; 6],
+        command: HunterFanCommand,
+    },
+    /// See `Sender::send_security1`
+    Security1 {
+        sub_type: Security1SubType,
+        id: RemoteId,
+        status: Security1Status,
+    },
+    /// See `Sender::send_camera`
+    Camera {
+        house_code: HouseCode,
+        command: CameraCommand,
+    },
+    /// See `Sender::send_remote`
+    Remote {
+        sub_type: RemoteSubType,
+        id: RemoteId,
+        command: u8,
+        toggle: bool,
+    },
+    /// See `Sender::send_thermostat1`
+    Thermostat1 {
+        sub_type: Thermostat1SubType,
+        id: RemoteId,
+        temperature: u8,
+        set_point: u8,
+        status: Thermostat1Status,
+        mode: Thermostat1Mode,
+    },
+    /// See `Sender::send_thermostat3`
+    Thermostat3 {
+        sub_type: Thermostat3SubType,
+        unit_id: RemoteId,
+        command: Thermostat3Command,
+    },
+}
+
+/// A step of a `Scene`
+#[derive(Clone, Debug, PartialEq)]
+pub enum SceneStep {
+    /// Sends a command and waits for the device to acknowledge it
+    Command(SceneCommand),
+    /// Waits before the next step
+    Delay(Duration),
+}
+
+/// Commands sent in order, with optional delays between them, see `Sender::run_scene`.
+///
+/// ```
+/// use rfxtrx433::{RemoteId, RfyCommand, RfySubType, Scene, SceneCommand, UnitCode};
+/// use std::time::Duration;
+///
+/// # fn main() -> rfxtrx433::Result<()> {
+/// let all_down = Scene::new()
+///     .command(SceneCommand::Rfy {
+///         sub_type: RfySubType::Rfy,
+///         id: RemoteId::from(0x010203),
+///         unit_code: UnitCode::new(1)?,
+///         command: RfyCommand::Down,
+///     })
+///     .delay(Duration::from_millis(500))
+///     .command(SceneCommand::Rfy {
+///         sub_type: RfySubType::Rfy,
+///         id: RemoteId::from(0x010203),
+///         unit_code: UnitCode::new(2)?,
+///         command: RfyCommand::Down,
+///     });
+/// assert_eq!(3, all_down.steps().len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scene {
+    steps: Vec<SceneStep>,
+}
+
+impl Scene {
+    /// Creates an empty scene
+    pub fn new() -> Self {
+        Scene::default()
+    }
+
+    /// Adds a command
+    pub fn command(mut self, command: SceneCommand) -> Self {
+        self.steps.push(SceneStep::Command(command));
+        self
+    }
+
+    /// Adds a delay before the next command
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.steps.push(SceneStep::Delay(delay));
+        self
+    }
+
+    /// The steps in the order they are run
+    pub fn steps(&self) -> &[SceneStep] {
+        &self.steps
+    }
+}
+
+impl Sender {
+    /// Runs the steps of `scene` in order, every command is acknowledged by the device
+    /// before the next step. The repeat policy applies to the commands.
+    ///
+    /// Returns the results of the commands. A command that the device failed to
+    /// transmit doesn't stop the scene, other errors do.
+    pub async fn run_scene(
+        &mut self,
+        scene: &Scene,
+    ) -> Result<Vec<std::result::Result<TransmitResult, TransmitError>>> {
+        debug!("Running scene with {} steps", scene.steps.len());
+        let mut results = Vec::new();
+        for step in &scene.steps {
+            match step {
+                SceneStep::Command(command) => match self.send_command(command).await {
+                    Ok(result) => results.push(Ok(result)),
+                    Err(TRXError::TransmitFailed(e)) => results.push(Err(e)),
+                    Err(e) => return Err(e),
+                },
+                SceneStep::Delay(delay) => runtime::sleep(*delay).await,
+            }
+        }
+        Ok(results)
+    }
+
+    /// Sends a single command, see `SceneCommand`
+    pub async fn send_command(&mut self, command: &SceneCommand) -> Result<TransmitResult> {
+        match *command {
+            SceneCommand::Lighting1 {
+                sub_type,
+                house_code,
+                unit_code,
+                command,
+            } => {
+                self.send_lighting1(sub_type, house_code, unit_code, command)
+                    .await
+            }
+            SceneCommand::Lighting2 {
+                sub_type,
+                id,
+                unit_code,
+                command,
+                level,
+            } => {
+                self.send_lighting2(sub_type, id, unit_code, command, level)
+                    .await
+            }
+            SceneCommand::Lighting3 {
+                system,
+                channels,
+                command,
+            } => self.send_lighting3(system, channels, command).await,
+            SceneCommand::Lighting5 {
+                sub_type,
+                id,
+                unit_code,
+                command,
+                level,
+            } => {
+                self.send_lighting5(sub_type, id, unit_code, command, level)
+                    .await
+            }
+            SceneCommand::Lighting6 {
+                sub_type,
+                id,
+                group_code,
+                unit_code,
+                command,
+            } => {
+                self.send_lighting6(sub_type, id, group_code, unit_code, command)
+                    .await
+            }
+            SceneCommand::Chime {
+                sub_type,
+                id,
+                sound,
+            } => self.send_chime(sub_type, id, sound).await,
+            SceneCommand::Rfy {
+                sub_type,
+                id,
+                unit_code,
+                command,
+            } => self.send_rfy(sub_type, id, unit_code, command).await,
+            SceneCommand::HomeConfort {
+                id,
+                house_code,
+                unit_code,
+                command,
+            } => {
+                self.send_home_confort(id, house_code, unit_code, command)
+                    .await
+            }
+            SceneCommand::HunterFan { id, command } => self.send_hunter_fan(id, command).await,
+            SceneCommand::Security1 {
+                sub_type,
+                id,
+                status,
+            } => self.send_security1(sub_type, id, status).await,
+            SceneCommand::Camera {
+                house_code,
+                command,
+            } => self.send_camera(house_code, command).await,
+            SceneCommand::Remote {
+                sub_type,
+                id,
+                command,
+                toggle,
+            } => self.send_remote(sub_type, id, command, toggle).await,
+            SceneCommand::Thermostat1 {
+                sub_type,
+                id,
+                temperature,
+                set_point,
+                status,
+                mode,
+            } => {
+                self.send_thermostat1(sub_type, id, temperature, set_point, status, mode)
+                    .await
+            }
+            SceneCommand::Thermostat3 {
+                sub_type,
+                unit_id,
+                command,
+            } => self.send_thermostat3(sub_type, unit_id, command).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::RFXtrx433;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn run_scene() {
+        let (transport, mut device) = tokio::io::duplex(256);
+        let mut rfx = RFXtrx433::new_from_transport(transport).await.unwrap();
+
+        tokio::spawn(async move {
+            // Not acknowledged, then acknowledged
+            for ack in [0x02, 0x00] {
+                let mut request = [0u8; 8];
+                device.read_exact(&mut request).await.unwrap();
+                assert_eq!(0x10, request[1]);
+                device
+                    .write_all(&[0x04, 0x02, 0x01, request[3], ack])
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let off = |unit| SceneCommand::Lighting1 {
+            sub_type: Lighting1SubType::Arc,
+            house_code: HouseCode::new('A').unwrap(),
+            unit_code: UnitCode::new(unit).unwrap(),
+            command: Lighting1Command::Off,
+        };
+        let scene = Scene::new()
+            .command(off(1))
+            .delay(Duration::from_millis(1))
+            .command(off(2));
+        let results = rfx.run_scene(&scene).await.unwrap();
+        assert_eq!(
+            vec![
+                Err(TransmitError::TransmitterDidNotLock),
+                Ok(TransmitResult::Ack)
+            ],
+            results
+        );
+    }
+}
@@ -1,71 +1,132 @@
+use crate::ids::{HouseCode, RemoteId, SensorId, UnitCode};
 use crate::protocols::*;
 use crate::{Result, TRXError};
 use log::{error, trace};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 pub(crate) type SequenceNumber = u8;
 
-#[derive(Clone, Copy, Debug, FromPrimitive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(u8)]
+/// Packet types of the RFXtrx protocol, the first byte after the length
 pub enum PacketType {
+    /// Interface control commands
     InterfaceControl = 0x00,
+    /// Interface responses
     InterfaceMessage = 0x01,
+    /// Receiver/transmitter responses
     RecXmitMessage = 0x02,
+    /// Undecoded RF messages
     Undecoded = 0x03,
+    /// X10, ARC, ELRO and similar
     Lighting1 = 0x10,
+    /// AC, HomeEasy EU, ANSLUT and Kambrook
     Lighting2 = 0x11,
+    /// Ikea Koppla
     Lighting3 = 0x12,
+    /// PT2262 and compatible
     Lighting4 = 0x13,
+    /// LightwaveRF, EMW100 and similar
     Lighting5 = 0x14,
+    /// Blyss and Cuveo
     Lighting6 = 0x15,
+    /// Door chimes
     Chime = 0x16,
+    /// Fans
     Fan = 0x17,
+    /// Harrison curtains
     Curtain = 0x18,
+    /// Blinds
     Blinds = 0x19,
+    /// Somfy RTS
     RFY = 0x1A,
+    /// Home Confort
     HomeConfort = 0x1B,
+    /// Gira/Jung Funkbus
     Funkbus = 0x1E,
+    /// Hunter fans
     Hunter = 0x1F,
+    /// X10, KD101 and Visonic security sensors
     Security1 = 0x20,
+    /// KeeLoq security remotes
     Security2 = 0x21,
+    /// X10 Ninja camera
     Camera = 0x28,
+    /// ATI and Medion remotes
     Remote = 0x30,
+    /// Digimax thermostats
     Thermostat1 = 0x40,
+    /// HE105 and RTS10 thermostats
     Thermostat2 = 0x41,
+    /// Mertik-Maxitrol fireplaces
     Thermostat3 = 0x42,
+    /// MCZ pellet stoves
     Thermostat4 = 0x43,
+    /// Smartwares radiator valves
     Radiator1 = 0x48,
+    /// BBQ thermometers
     BBQ = 0x4E,
+    /// Temperature and rain sensors
     TempRain = 0x4F,
+    /// Temperature sensors
     TEMP = 0x50,
+    /// Humidity sensors
     HUM = 0x51,
+    /// Temperature and humidity sensors
     TempHum = 0x52,
+    /// Barometric sensors
     BARO = 0x53,
+    /// Temperature, humidity and barometric sensors
     TempHumBaro = 0x54,
+    /// Rain gauges
     RAIN = 0x55,
+    /// Wind sensors
     WIND = 0x56,
+    /// UV sensors
     UV = 0x57,
+    /// Date and time sensors
     DT = 0x58,
+    /// Current meters
     CURRENT = 0x59,
+    /// Energy meters
     ENERGY = 0x5A,
+    /// Current and energy meters
     CURRENTENERGY = 0x5B,
+    /// Power meters
     POWER = 0x5C,
+    /// Scales
     WEIGHT = 0x5D,
+    /// Gas meters
     GAS = 0x5E,
+    /// Water meters
     WATER = 0x5F,
+    /// Cartelectronic meters
     CARTELECTRONIC = 0x60,
+    /// Async serial port configuration
     ASYNCPORT = 0x61,
+    /// Async serial port data
     ASYNCDATA = 0x62,
+    /// RFXSensor
     RFXSensor = 0x70,
+    /// RFXMeter
     RFXMeter = 0x71,
+    /// FS20
     FS20 = 0x72,
+    /// Weather stations
     WEATHER = 0x76,
+    /// Solar sensors
     SOLAR = 0x77,
+    /// Raw pulse trains
     RAW = 0x7F,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Header of a packet, after the length byte
 pub struct PacketHeader {
     packet_type: PacketType,
     sub_type: u8,
@@ -73,6 +134,21 @@ pub struct PacketHeader {
 }
 
 impl PacketHeader {
+    /// Packet type
+    pub fn packet_type(&self) -> PacketType {
+        self.packet_type
+    }
+
+    /// Sub type, its meaning depends on the packet type
+    pub fn sub_type(&self) -> u8 {
+        self.sub_type
+    }
+
+    /// Sequence number, set by the sender
+    pub fn seqnbr(&self) -> u8 {
+        self.seqnbr
+    }
+
     fn extend(&self, v: &mut Vec<u8>) {
         v.push(0); // placeholder for size
         v.push(self.packet_type as u8);
@@ -104,6 +180,28 @@ impl PacketHeader {
             &data[3..],
         ))
     }
+
+    fn parse_sub_type<T: FromPrimitive>(&self) -> Result<T> {
+        T::from_u8(self.sub_type).ok_or(TRXError::UnknownSubPacketType {
+            packet_type: self.packet_type,
+            sub_type: self.sub_type,
+        })
+    }
+
+    fn parse_command<T: FromPrimitive>(&self, command: u8) -> Result<T> {
+        T::from_u8(command).ok_or(TRXError::UnknownCommand {
+            packet_type: self.packet_type,
+            command,
+        })
+    }
+
+    fn parse_value<T: FromPrimitive>(&self, field: &'static str, value: u8) -> Result<T> {
+        T::from_u8(value).ok_or(TRXError::InvalidValue {
+            packet_type: self.packet_type,
+            field,
+            value,
+        })
+    }
 }
 
 #[derive(Clone, Copy, FromPrimitive, Debug)]
@@ -112,8 +210,10 @@ enum InterfaceCommandCmd {
     Reset = 0,
     Status = 0x02,
     SetMode = 0x03,
+    EnableAll = 0x04,
     Save = 0x06,
     StartReceiver = 0x07,
+    NoiseLevel = 0x0B,
 }
 
 struct InterfaceCommand {
@@ -138,6 +238,7 @@ impl InterfaceCommand {
 }
 
 #[derive(Debug, Clone, Copy)]
+/// Protocols enabled in the device
 pub struct EnabledProtocols {
     protos_1: Protocols1,
     protos_2: Protocols2,
@@ -145,6 +246,48 @@ pub struct EnabledProtocols {
     protos_4: Protocols4,
 }
 
+impl EnabledProtocols {
+    /// Enabled protocols in the first mode byte
+    pub fn protos_1(&self) -> Protocols1 {
+        self.protos_1
+    }
+    /// Enabled protocols in the second mode byte
+    pub fn protos_2(&self) -> Protocols2 {
+        self.protos_2
+    }
+    /// Enabled protocols in the third mode byte
+    pub fn protos_3(&self) -> Protocols3 {
+        self.protos_3
+    }
+    /// Enabled protocols in the fourth mode byte
+    pub fn protos_4(&self) -> Protocols4 {
+        self.protos_4
+    }
+    /// Enabled protocols in the first mode byte of a 868 MHz device
+    pub fn protos_868_1(&self) -> Protocols868_1 {
+        Protocols868_1::from_bits_truncate(self.protos_1.bits())
+    }
+    /// Enabled protocols in the second mode byte of a 868 MHz device
+    pub fn protos_868_2(&self) -> Protocols868_2 {
+        Protocols868_2::from_bits_truncate(self.protos_2.bits())
+    }
+    /// Enabled protocols in the third mode byte of a 868 MHz device
+    pub fn protos_868_3(&self) -> Protocols868_3 {
+        Protocols868_3::from_bits_truncate(self.protos_3.bits())
+    }
+    /// Enabled protocols in the fourth mode byte of a 868 MHz device
+    pub fn protos_868_4(&self) -> Protocols868_4 {
+        Protocols868_4::from_bits_truncate(self.protos_4.bits())
+    }
+    pub(crate) fn to_bytes(self) -> [u8; 4] {
+        [
+            self.protos_1.bits(),
+            self.protos_2.bits(),
+            self.protos_3.bits(),
+            self.protos_4.bits(),
+        ]
+    }
+}
 impl Default for EnabledProtocols {
     fn default() -> Self {
         EnabledProtocols {
@@ -194,16 +337,25 @@ impl Default for Frequency {
     }
 }
 
-#[derive(Clone, Copy, Debug, FromPrimitive)]
+#[derive(Clone, Copy, Debug, FromPrimitive, PartialEq)]
 #[repr(u8)]
+/// Firmware type reported in the status response
 pub enum FWType {
+    /// Receiver only firmware
     TypeRec = 0x0,
+    /// Type 1 firmware
     Type1 = 0x1,
+    /// Type 2 firmware
     Type2 = 0x2,
+    /// Ext firmware
     TypeExt = 0x3,
+    /// Ext2 firmware
     TypeExt2 = 0x4,
+    /// Pro1 firmware
     TypePro1 = 0x5,
+    /// Pro2 firmware
     TypePro2 = 0x6,
+    /// ProXL1 firmware
     TypeProXL1 = 0x10,
 }
 
@@ -213,10 +365,44 @@ pub enum InterfaceMessage {
         frequency: Frequency,
         fw_version: u8,
         enabled_protocols: EnabledProtocols,
+        hw_version_major: u8,
+        hw_version_minor: u8,
+        output_power: u8,
+        fw_type: Option<FWType>,
+        device_name: Option<String>,
     },
     SetMode,
-    ReceiverStarted,
+    ReceiverStarted(String),
     Save,
+    TransmitResponse(std::result::Result<TransmitResult, TransmitError>),
+    UnknownRfyRemote,
+    RfyRemoteList(RfyRemote),
+    AsaRemoteList(RfyRemote),
+    NoiseLevel(u8),
+    ExtError(u8),
+    WrongCommand(u8),
+}
+
+#[derive(Clone, Copy, Debug)]
+/// RFY or ASA remote stored in the device, as reported when listing remotes
+pub struct RfyRemote {
+    /// Storage location in the device
+    pub location: u8,
+    /// 20 bit remote id
+    pub id: RemoteId,
+    /// Unit code
+    pub unit_code: UnitCode,
+}
+
+impl RfyRemote {
+    fn parse(data: &[u8]) -> Result<Self> {
+        check_len(data, 5)?;
+        Ok(Self {
+            location: data[0],
+            id: RemoteId::from((data[1] as u32) << 16 | (data[2] as u32) << 8 | data[3] as u32),
+            unit_code: UnitCode::new(data[4])?,
+        })
+    }
 }
 
 impl InterfaceMessage {
@@ -227,32 +413,122 @@ impl InterfaceMessage {
                 sub_type: header.sub_type,
             },
         )?;
-        let cmd = InterfaceCommandCmd::from_u8(data[0])
-            .ok_or(TRXError::UnknownInterfaceMessageCommand(data[0]))?;
-        trace!(
-            "Received InterfaceMeessage sub_type: {:?} cmd: {:?}",
-            sub_type,
-            cmd
-        );
+        trace!("Received InterfaceMessage sub_type: {:?}", sub_type);
         match sub_type {
-            InterfaceMessageSubType::InterfaceResponse => match cmd {
-                InterfaceCommandCmd::Status => Ok(InterfaceMessage::Status {
-                    frequency:
-                        Frequency::from_u8(data[1]) //.unwrap_or(HWType::Unknown),
-                            .ok_or(TRXError::UnknownHardwareType(data[1]))?,
-                    fw_version: data[2],
-                    enabled_protocols: data[3..7].into(),
-                }),
-                InterfaceCommandCmd::SetMode => Ok(InterfaceMessage::SetMode),
-                InterfaceCommandCmd::Save => Ok(InterfaceMessage::Save),
-
-                cmd => {
-                    error!("No code to handle {:?}", cmd);
-                    unreachable!();
+            InterfaceMessageSubType::InterfaceResponse => {
+                check_len(data, 1)?;
+                let cmd = InterfaceCommandCmd::from_u8(data[0])
+                    .ok_or(TRXError::UnknownInterfaceMessageCommand(data[0]))?;
+                trace!("Received interface response cmd: {:?}", cmd);
+                match cmd {
+                    InterfaceCommandCmd::Status => {
+                        check_len(data, 10)?;
+                        // Firmware type and device name were added in later firmware versions
+                        let device_name = data
+                            .get(11..)
+                            .map(|name| {
+                                String::from_utf8_lossy(name)
+                                    .trim_end_matches(char::from(0))
+                                    .to_string()
+                            })
+                            .filter(|name| !name.is_empty());
+                        Ok(InterfaceMessage::Status {
+                            frequency:
+                                Frequency::from_u8(data[1]) //.unwrap_or(HWType::Unknown),
+                                    .ok_or(TRXError::UnknownHardwareType(data[1]))?,
+                            fw_version: data[2],
+                            enabled_protocols: data[3..7].into(),
+                            hw_version_major: data[7],
+                            hw_version_minor: data[8],
+                            output_power: data[9],
+                            fw_type: data.get(10).and_then(|t| FWType::from_u8(*t)),
+                            device_name,
+                        })
+                    }
+                    InterfaceCommandCmd::SetMode | InterfaceCommandCmd::EnableAll => {
+                        Ok(InterfaceMessage::SetMode)
+                    }
+                    InterfaceCommandCmd::Save => Ok(InterfaceMessage::Save),
+                    InterfaceCommandCmd::NoiseLevel => {
+                        check_len(data, 2)?;
+                        Ok(InterfaceMessage::NoiseLevel(data[1]))
+                    }
+
+                    cmd => {
+                        error!("No code to handle {:?}", cmd);
+                        Err(TRXError::UnknownInterfaceMessageCommand(cmd as u8))
+                    }
                 }
-            },
-            InterfaceMessageSubType::RecStarted => Ok(InterfaceMessage::ReceiverStarted),
-            _ => unreachable!(),
+            }
+            InterfaceMessageSubType::UnknownRFYremote => Ok(InterfaceMessage::UnknownRfyRemote),
+            InterfaceMessageSubType::RFYremoteList => {
+                Ok(InterfaceMessage::RfyRemoteList(RfyRemote::parse(data)?))
+            }
+            InterfaceMessageSubType::ASAremoteList => {
+                Ok(InterfaceMessage::AsaRemoteList(RfyRemote::parse(data)?))
+            }
+            InterfaceMessageSubType::RecStarted => Ok(InterfaceMessage::ReceiverStarted(
+                String::from_utf8_lossy(data.get(1..).unwrap_or_default())
+                    .trim_end_matches(char::from(0))
+                    .to_string(),
+            )),
+            InterfaceMessageSubType::ExtError => {
+                check_len(data, 1)?;
+                Ok(InterfaceMessage::ExtError(data[0]))
+            }
+            InterfaceMessageSubType::InterfaceWrongCommand => {
+                check_len(data, 1)?;
+                Ok(InterfaceMessage::WrongCommand(data[0]))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Successful response to a transmit command
+pub enum TransmitResult {
+    /// Transmitted
+    Ack,
+    /// Transmitted after a delay, the channel was busy
+    AckDelayed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Reason the device refused a transmit command
+pub enum TransmitError {
+    /// The receiver did not lock
+    ReceiverDidNotLock,
+    /// The transmitter did not lock on the requested frequency
+    TransmitterDidNotLock,
+    /// AC address zero in id1 - id4 is not allowed
+    InvalidAcAddress,
+}
+
+#[derive(Clone, Copy, FromPrimitive, Debug)]
+#[repr(u8)]
+enum RecXmitMessageSubType {
+    ReceiverLockError = 0x00,
+    TransmitterResponse = 0x01,
+}
+
+fn parse_transmit_response(
+    header: PacketHeader,
+    data: &[u8],
+) -> Result<std::result::Result<TransmitResult, TransmitError>> {
+    match header.parse_sub_type()? {
+        RecXmitMessageSubType::ReceiverLockError => Ok(Err(TransmitError::ReceiverDidNotLock)),
+        RecXmitMessageSubType::TransmitterResponse => {
+            check_len(data, 1)?;
+            match data[0] {
+                0x00 => Ok(Ok(TransmitResult::Ack)),
+                0x01 => Ok(Ok(TransmitResult::AckDelayed)),
+                0x02 => Ok(Err(TransmitError::TransmitterDidNotLock)),
+                0x03 => Ok(Err(TransmitError::InvalidAcAddress)),
+                response => Err(TRXError::UnexpectedMessage(format!(
+                    "Unknown transmit response {:02X}",
+                    response
+                ))),
+            }
         }
     }
 }
@@ -280,11 +556,91 @@ pub(crate) enum ReceivedCommand {
     ProtocolMessage(ProtocolMessage),
 }
 
-#[derive(Debug)]
+/// Unit of `ReceivedMessage::temperature`, see `RFXtrx433Builder::temperature_unit`.
+/// The temperature fields of the messages are always in Celsius.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TemperatureUnit {
+    /// Celsius, as sent by the device (default)
+    #[default]
+    Celsius,
+    /// Fahrenheit
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Converts a temperature in Celsius to this unit
+    pub fn convert(self, celsius: f32) -> f32 {
+        match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius_to_fahrenheit(celsius),
+        }
+    }
+
+    /// Symbol of the unit, e.g. "°C"
+    pub fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 /// Returned value from reading protocol messages
 pub enum ProtocolMessage {
+    /// Undecoded messages
+    Undecoded(Undecoded),
+    /// Lighting1, X10, ARC etc
+    Lighting1(Lighting1),
+    /// Lighting2, AC, HomeEasy EU etc
+    Lighting2(Lighting2),
+    /// Lighting4, PT2262
+    Lighting4(Lighting4),
+    /// Fan remote controls
+    Fan(Fan),
+    /// Curtain, Harrison
+    Curtain(Curtain),
+    /// HomeConfort, TEL-010
+    HomeConfort(HomeConfort),
+    /// Funkbus, Gira and Insta
+    Funkbus(Funkbus),
+    /// Hunter fan
+    HunterFan(HunterFan),
+    /// Security1, X10, Visonic, Meiantech etc
+    Security1(Security1),
+    /// Security2, KeeLoq
+    Security2(Security2),
+    /// Camera, X10 Ninja
+    Camera(Camera),
+    /// Remote control, ATI and Medion
+    RemoteControl(RemoteControl),
+    /// BBQ thermometer
+    Bbq(Bbq),
+    /// Temperature & rain
+    TempRain(TempRain),
+    /// Temperature
+    Temp(Temp),
+    /// Humidity
+    Hum(Hum),
     /// Temperature & humidity
     TempHum(TempHum),
+    /// Temperature, humidity & barometer
+    TempHumBaro(TempHumBaro),
+    /// Energy usage
+    Energy(Energy),
+    /// Power meter
+    Power(Power),
+    /// Weight scale
+    Weight(Weight),
+    /// Async port configuration
+    AsyncPort(AsyncPort),
+    /// Async port data
+    AsyncData(AsyncData),
+    /// Weather station
+    WeatherStation(WeatherStation),
+    /// Raw pulse train
+    RawPulses(RawPulses),
     /// Raw data
     NotParsed {
         /// Packet header
@@ -294,221 +650,4148 @@ pub enum ProtocolMessage {
     },
 }
 
+impl ProtocolMessage {
+    /// Temperature in Celsius of sensor messages, the food probe temperature for
+    /// BBQ thermometers. None for messages without a temperature.
+    pub fn temperature(&self) -> Option<f32> {
+        match self {
+            ProtocolMessage::Bbq(msg) => Some(msg.food_temp),
+            ProtocolMessage::TempRain(msg) => Some(msg.temp),
+            ProtocolMessage::Temp(msg) => Some(msg.temp),
+            ProtocolMessage::TempHum(msg) => Some(msg.temp),
+            ProtocolMessage::TempHumBaro(msg) => Some(msg.temp),
+            ProtocolMessage::WeatherStation(msg) => Some(msg.temp),
+            _ => None,
+        }
+    }
+
+    /// Signal strength of the message, None for messages without one, e.g. undecoded
+    /// or raw messages
+    pub fn rssi(&self) -> Option<Rssi> {
+        match self {
+            ProtocolMessage::Lighting1(msg) => Some(msg.rssi),
+            ProtocolMessage::Lighting2(msg) => Some(msg.rssi),
+            ProtocolMessage::Lighting4(msg) => Some(msg.rssi),
+            ProtocolMessage::Fan(msg) => Some(msg.rssi),
+            ProtocolMessage::Curtain(msg) => Some(msg.rssi),
+            ProtocolMessage::HomeConfort(msg) => Some(msg.rssi),
+            ProtocolMessage::Funkbus(msg) => Some(msg.rssi),
+            ProtocolMessage::HunterFan(msg) => Some(msg.rssi),
+            ProtocolMessage::Security1(msg) => Some(msg.rssi),
+            ProtocolMessage::Security2(msg) => Some(msg.rssi),
+            ProtocolMessage::Camera(msg) => Some(msg.rssi),
+            ProtocolMessage::RemoteControl(msg) => Some(msg.rssi),
+            ProtocolMessage::Bbq(msg) => Some(msg.rssi),
+            ProtocolMessage::TempRain(msg) => Some(msg.rssi),
+            ProtocolMessage::Temp(msg) => Some(msg.rssi),
+            ProtocolMessage::Hum(msg) => Some(msg.rssi),
+            ProtocolMessage::TempHum(msg) => Some(msg.rssi),
+            ProtocolMessage::TempHumBaro(msg) => Some(msg.rssi),
+            ProtocolMessage::Energy(msg) => Some(msg.rssi),
+            ProtocolMessage::Power(msg) => Some(msg.rssi),
+            ProtocolMessage::Weight(msg) => Some(msg.rssi),
+            ProtocolMessage::WeatherStation(msg) => Some(msg.rssi),
+            ProtocolMessage::Undecoded(_)
+            | ProtocolMessage::AsyncPort(_)
+            | ProtocolMessage::AsyncData(_)
+            | ProtocolMessage::RawPulses(_)
+            | ProtocolMessage::NotParsed { .. } => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Humidity status reported by humidity sensors
+pub enum HumidityStatus {
+    /// Normal
+    Normal,
+    /// Comfortable
+    Comfort,
+    /// Dry
+    Dry,
+    /// Wet
+    Wet,
+    /// A status this library doesn't know, with its value
+    Unknown(u8),
+}
+
+impl From<u8> for HumidityStatus {
+    fn from(status: u8) -> Self {
+        match status {
+            0x00 => HumidityStatus::Normal,
+            0x01 => HumidityStatus::Comfort,
+            0x02 => HumidityStatus::Dry,
+            0x03 => HumidityStatus::Wet,
+            status => HumidityStatus::Unknown(status),
+        }
+    }
+}
+
+impl From<HumidityStatus> for u8 {
+    fn from(status: HumidityStatus) -> u8 {
+        match status {
+            HumidityStatus::Normal => 0x00,
+            HumidityStatus::Comfort => 0x01,
+            HumidityStatus::Dry => 0x02,
+            HumidityStatus::Wet => 0x03,
+            HumidityStatus::Unknown(status) => status,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Battery status reported by battery powered sensors. Most sensors only report
+/// low or ok, some report a level in between.
+pub enum BatteryStatus {
+    /// Battery low, level 0
+    Low,
+    /// Level 1 - 8
+    Level(u8),
+    /// Battery ok, level 9 and above
+    Ok,
+}
+
+impl BatteryStatus {
+    /// Returns true if the battery should be replaced
+    pub fn is_low(self) -> bool {
+        self.percentage() <= 20
+    }
+
+    /// Approximate remaining capacity in percent, 10 - 100
+    pub fn percentage(self) -> u8 {
+        match self {
+            BatteryStatus::Low => 10,
+            // Levels above 8 can only be constructed directly, they're full
+            BatteryStatus::Level(level) => (level.min(9) + 1) * 10,
+            BatteryStatus::Ok => 100,
+        }
+    }
+}
+
+impl From<u8> for BatteryStatus {
+    /// From the battery level nibble
+    fn from(level: u8) -> Self {
+        match level & 0x0f {
+            0 => BatteryStatus::Low,
+            level @ 1..=8 => BatteryStatus::Level(level),
+            _ => BatteryStatus::Ok,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+/// Received signal strength, the 0 - 15 level reported by the device
+pub struct Rssi(u8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Qualitative signal strength, see `Rssi::quality`
+pub enum SignalQuality {
+    /// Level 0
+    NoSignal,
+    /// Level 1 - 5
+    Weak,
+    /// Level 6 - 10
+    Good,
+    /// Level 11 - 15
+    Excellent,
+}
+
+impl Rssi {
+    /// Returns the level, 0 - 15
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Rough estimate of the signal strength in dBm, assuming the levels are 4 dB
+    /// steps from -100 dBm at level 0 to -40 dBm at level 15. The device only
+    /// reports the level, not a calibrated value.
+    pub fn as_dbm(self) -> i16 {
+        -100 + 4 * self.0 as i16
+    }
+
+    /// Qualitative signal strength
+    pub fn quality(self) -> SignalQuality {
+        match self.0 {
+            0 => SignalQuality::NoSignal,
+            1..=5 => SignalQuality::Weak,
+            6..=10 => SignalQuality::Good,
+            _ => SignalQuality::Excellent,
+        }
+    }
+}
+
+impl From<u8> for Rssi {
+    /// From the signal strength nibble, already shifted down
+    fn from(level: u8) -> Self {
+        Rssi(level & 0x0f)
+    }
+}
+
+impl From<Rssi> for u8 {
+    fn from(rssi: Rssi) -> u8 {
+        rssi.0
+    }
+}
+
+impl PartialEq<u8> for Rssi {
+    fn eq(&self, other: &u8) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Rssi> for u8 {
+    fn eq(&self, other: &Rssi) -> bool {
+        *self == other.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Sensor models using the TempHum packet
+pub enum TempHumModel {
+    /// THGN122/123, THGN132, THGR122/228/238/268
+    Thgn122,
+    /// THGR810, THGN800
+    Thgr810,
+    /// RTGR328
+    Rtgr328,
+    /// THGR328
+    Thgr328,
+    /// WTGR800
+    Wtgr800,
+    /// THGR918/928, THGRN228, THGN500
+    Thgr918,
+    /// TFA TS34C, Cresta
+    Cresta,
+    /// WT260, WT260H, WT440H, WT450, WT450H
+    Wt450,
+    /// Viking 02035, 02038
+    Viking,
+    /// Rubicson
+    Rubicson,
+    /// EW109
+    Ew109,
+    /// Imagintronix, Opus XT300
+    Imagintronix,
+    /// Alecto WS1700 and compatibles
+    AlectoWs1700,
+    /// Alecto WS3500, WS4500, Auriol H13726, Hama EWS1500
+    AlectoWs4500,
+    /// A model this library doesn't know, with its sub type
+    Unknown(u8),
+}
+
+impl From<u8> for TempHumModel {
+    /// From the sub type of the packet
+    fn from(sub_type: u8) -> Self {
+        match sub_type {
+            0x01 => TempHumModel::Thgn122,
+            0x02 => TempHumModel::Thgr810,
+            0x03 => TempHumModel::Rtgr328,
+            0x04 => TempHumModel::Thgr328,
+            0x05 => TempHumModel::Wtgr800,
+            0x06 => TempHumModel::Thgr918,
+            0x07 => TempHumModel::Cresta,
+            0x08 => TempHumModel::Wt450,
+            0x09 => TempHumModel::Viking,
+            0x0A => TempHumModel::Rubicson,
+            0x0B => TempHumModel::Ew109,
+            0x0C => TempHumModel::Imagintronix,
+            0x0D => TempHumModel::AlectoWs1700,
+            0x0E => TempHumModel::AlectoWs4500,
+            sub_type => TempHumModel::Unknown(sub_type),
+        }
+    }
+}
+
+impl From<TempHumModel> for u8 {
+    fn from(model: TempHumModel) -> u8 {
+        match model {
+            TempHumModel::Thgn122 => 0x01,
+            TempHumModel::Thgr810 => 0x02,
+            TempHumModel::Rtgr328 => 0x03,
+            TempHumModel::Thgr328 => 0x04,
+            TempHumModel::Wtgr800 => 0x05,
+            TempHumModel::Thgr918 => 0x06,
+            TempHumModel::Cresta => 0x07,
+            TempHumModel::Wt450 => 0x08,
+            TempHumModel::Viking => 0x09,
+            TempHumModel::Rubicson => 0x0A,
+            TempHumModel::Ew109 => 0x0B,
+            TempHumModel::Imagintronix => 0x0C,
+            TempHumModel::AlectoWs1700 => 0x0D,
+            TempHumModel::AlectoWs4500 => 0x0E,
+            TempHumModel::Unknown(sub_type) => sub_type,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Temperature and humidity
 pub struct TempHum {
-    pub id: u16,
+    /// Sensor model
+    pub model: TempHumModel,
+    /// Sensor id
+    pub id: SensorId,
+    /// Temperature in Celsius
     pub temp: f32,
+    /// Relative humidity in percent
     pub humidity: u8,
-    pub humidity_status: u8,
-    pub battery_level: u8,
-    pub rssi: u8,
+    /// Humidity status
+    pub humidity_status: HumidityStatus,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
 }
 
 impl TempHum {
-    fn parse(_header: PacketHeader, data: &[u8]) -> Result<Self> {
+    /// Temperature in Fahrenheit, converted from `temp` in Celsius
+    pub fn temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.temp)
+    }
+
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
         if data.len() < 7 {
             return Err(TRXError::NotEnoughData {
                 received: data.len(),
                 expected: 7,
             });
         }
-        let id = ((data[0] as u16) << 8) | data[1] as u16;
-
-        let temp_sign = data[2] & 0x80;
-        let temp_high = data[2] & 0x7f;
-        let temp_low = data[3];
+        // The readings are the same for all models, keep the ones of new models
+        let model = TempHumModel::from(header.sub_type);
+        let id = SensorId::from(((data[0] as u16) << 8) | data[1] as u16);
 
-        let temp = if temp_sign != 0 {
-            -((temp_high as i16) << 8 | temp_low as i16)
-        } else {
-            (temp_high as i16) << 8 | temp_low as i16
-        };
-
-        let temp = temp as f32 / 10.0;
+        let temp = parse_temp(data[2], data[3]);
 
         let humidity = data[4];
-        let humidity_status = data[5];
+        let humidity_status = HumidityStatus::from(data[5]);
 
-        let battery_level = data[6] >> 4;
-        let rssi = data[6] & 0x0f;
+        let battery = BatteryStatus::from(data[6]);
+        let rssi = Rssi::from(data[6] >> 4);
 
         Ok(Self {
+            model,
             id,
             temp,
             humidity,
             humidity_status,
-            battery_level,
+            battery,
             rssi,
         })
     }
 }
 
-pub(crate) fn reset(seqnbr: SequenceNumber) -> Vec<u8> {
-    InterfaceCommand {
-        header: PacketHeader {
-            packet_type: PacketType::InterfaceControl,
-            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
-            seqnbr,
-        },
-        cmd: InterfaceCommandCmd::Reset,
-        frequency: 0,
-        xmitpwr: 0,
-        extra: [0; 7],
-    }
-    .to_vec()
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Device types using the Lighting1 packet
+pub enum Lighting1SubType {
+    /// X10 lighting
+    X10 = 0x00,
+    /// ARC
+    Arc = 0x01,
+    /// ELRO AB400D, Flamingo, Sartano
+    Ab400d = 0x02,
+    /// Waveman
+    Waveman = 0x03,
+    /// Chacon EMW200
+    Emw200 = 0x04,
+    /// Impuls
+    Impuls = 0x05,
+    /// RisingSun
+    RisingSun = 0x06,
+    /// Philips SBC
+    Philips = 0x07,
+    /// Energenie ENER010
+    Energenie = 0x08,
+    /// Energenie 5-gang
+    Energenie5 = 0x09,
+    /// COCO GDR2-2000R
+    Gdr2 = 0x0A,
+    /// HQ COCO-20
+    Hq = 0x0B,
+    /// Oase Inscenio FM Master
+    Oase = 0x0C,
 }
 
-pub(crate) fn get_status(seqnbr: SequenceNumber) -> Vec<u8> {
-    InterfaceCommand {
-        header: PacketHeader {
-            packet_type: PacketType::InterfaceControl,
-            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
-            seqnbr,
-        },
-        cmd: InterfaceCommandCmd::Status,
-        frequency: 0,
-        xmitpwr: 0,
-        extra: [0; 7],
-    }
-    .to_vec()
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Lighting1 commands
+pub enum Lighting1Command {
+    /// Off
+    Off = 0x00,
+    /// On
+    On = 0x01,
+    /// Dim
+    Dim = 0x02,
+    /// Bright
+    Bright = 0x03,
+    /// All off for the house code
+    AllOff = 0x05,
+    /// All on for the house code
+    AllOn = 0x06,
+    /// Chime
+    Chime = 0x07,
 }
 
-pub(crate) fn start_receiver(seqnbr: SequenceNumber) -> Vec<u8> {
-    InterfaceCommand {
-        header: PacketHeader {
-            packet_type: PacketType::InterfaceControl,
-            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
-            seqnbr,
-        },
-        cmd: InterfaceCommandCmd::StartReceiver,
-        frequency: 0,
-        xmitpwr: 0,
-        extra: [0; 7],
-    }
-    .to_vec()
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Lighting1 message, used by X10, ARC and similar remotes
+pub struct Lighting1 {
+    /// Device type
+    pub sub_type: Lighting1SubType,
+    /// House code, 'A' - 'P'
+    pub house_code: HouseCode,
+    /// Unit code, 1 - 16
+    pub unit_code: UnitCode,
+    /// Received command
+    pub command: Lighting1Command,
+    /// Signal strength
+    pub rssi: Rssi,
 }
 
-pub(crate) fn parse_message(data: &[u8]) -> Result<ReceivedCommand> {
-    let (header, data) = PacketHeader::parse(data)?;
-
-    match header.packet_type {
-        PacketType::InterfaceMessage => Ok(ReceivedCommand::InterfaceMessage(
-            InterfaceMessage::parse(header, data)?,
-        )),
-        PacketType::TempHum => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHum(
-            TempHum::parse(header, data)?,
-        ))),
+impl Lighting1 {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 4)?;
+        let sub_type = header.parse_sub_type()?;
+        let command = header.parse_command(data[2])?;
 
-        // Catch all if we receive a command we don't know how to handle
-        _ => Ok(ReceivedCommand::ProtocolMessage(
-            ProtocolMessage::NotParsed {
-                header,
-                data: data.to_vec(),
-            },
-        )),
+        Ok(Self {
+            sub_type,
+            house_code: HouseCode::new(data[0] as char)?,
+            unit_code: UnitCode::new(data[1])?,
+            command,
+            rssi: Rssi::from(data[3] >> 4),
+        })
     }
 }
 
-pub(crate) fn set_mode(
-    seqnbr: SequenceNumber,
-    frequency: Frequency,
-    protos_1: Protocols1,
-    protos_2: Protocols2,
-    protos_3: Protocols3,
-    protos_4: Protocols4,
-) -> Vec<u8> {
-    InterfaceCommand {
-        header: PacketHeader {
-            packet_type: PacketType::InterfaceControl,
-            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
-            seqnbr,
-        },
-        cmd: InterfaceCommandCmd::SetMode,
-        frequency: frequency as u8,
-        xmitpwr: 0,
-        extra: [
-            protos_1.bits(),
-            protos_2.bits(),
-            protos_3.bits(),
-            protos_4.bits(),
-            0,
-            0,
-            0,
-        ],
-    }
-    .to_vec()
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Device types using the Lighting2 packet
+pub enum Lighting2SubType {
+    /// AC, KlikAanKlikUit, Chacon, DI.O
+    Ac = 0x00,
+    /// HomeEasy EU
+    HomeEasyEu = 0x01,
+    /// Anslut
+    Anslut = 0x02,
+    /// Kambrook
+    Kambrook = 0x03,
 }
 
-pub(crate) fn save(seqnbr: SequenceNumber) -> Vec<u8> {
-    InterfaceCommand {
-        header: PacketHeader {
-            packet_type: PacketType::InterfaceControl,
-            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
-            seqnbr,
-        },
-        cmd: InterfaceCommandCmd::Save,
-        frequency: 0,
-        xmitpwr: 0,
-        extra: [0; 7],
-    }
-    .to_vec()
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Lighting2 commands
+pub enum Lighting2Command {
+    /// Off
+    Off = 0x00,
+    /// On
+    On = 0x01,
+    /// Set dim level
+    SetLevel = 0x02,
+    /// Group off
+    GroupOff = 0x03,
+    /// Group on
+    GroupOn = 0x04,
+    /// Set group dim level
+    SetGroupLevel = 0x05,
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    #[test]
-    fn create_reset() {
-        let cmd = reset(1);
-        let cmd = cmd.to_vec();
-        assert_eq!(vec![0xd, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], cmd);
-    }
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Lighting2 message, used by AC and HomeEasy self-learning switches
+pub struct Lighting2 {
+    /// Device type
+    pub sub_type: Lighting2SubType,
+    /// 26 bit device address
+    pub id: RemoteId,
+    /// Unit code, 1 - 16
+    pub unit_code: UnitCode,
+    /// Received command
+    pub command: Lighting2Command,
+    /// Dim level, 0 - 15
+    pub level: u8,
+    /// Signal strength
+    pub rssi: Rssi,
+}
 
-    #[test]
-    fn create_save_settings() {
-        let cmd = save(0x11).to_vec();
-        assert_eq!(vec![0x0d, 00, 00, 0x11, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0], cmd);
-    }
+impl Lighting2 {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 8)?;
+        let sub_type = header.parse_sub_type()?;
+        let command = header.parse_command(data[5])?;
+
+        let id = RemoteId::from(
+            (data[0] as u32 & 0x03) << 24
+                | (data[1] as u32) << 16
+                | (data[2] as u32) << 8
+                | data[3] as u32,
+        );
+
+        Ok(Self {
+            sub_type,
+            id,
+            unit_code: UnitCode::new(data[4])?,
+            command,
+            level: data[6],
+            rssi: Rssi::from(data[7] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Lighting4 message, used by PT2262 and compatible devices
+pub struct Lighting4 {
+    /// Sub type
+    pub sub_type: u8,
+    /// 24 bit code
+    pub code: u32,
+    /// Pulse length in microseconds, needed to replay the code
+    pub pulse: u16,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Lighting4 {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 6)?;
+        let code = (data[0] as u32) << 16 | (data[1] as u32) << 8 | data[2] as u32;
+        let pulse = (data[3] as u16) << 8 | data[4] as u16;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            code,
+            pulse,
+            rssi: Rssi::from(data[5] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Device types using the Fan packet
+pub enum FanSubType {
+    /// Siemens SF01
+    SiemensSf01 = 0x00,
+    /// Itho CVE RFT
+    Itho = 0x01,
+    /// Lucci Air AC
+    LucciAir = 0x02,
+    /// SEAV TXS4
+    SeavTxs4 = 0x03,
+    /// Westinghouse 7226640
+    Westinghouse = 0x04,
+    /// Lucci Air DC
+    LucciAirDc = 0x05,
+    /// Casafan
+    Casafan = 0x06,
+    /// FT1211R
+    Ft1211r = 0x07,
+    /// Falmec
+    Falmec = 0x08,
+    /// Lucci Air DCII
+    LucciAirDcII = 0x09,
+    /// Itho CVE ECO RFT
+    IthoEco = 0x0A,
+    /// Novy
+    Novy = 0x0B,
+    /// Orcon
+    Orcon = 0x0C,
+    /// Itho HRU400
+    IthoHru400 = 0x0D,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Fan message, used by fan remote controls
+pub struct Fan {
+    /// Device type
+    pub sub_type: FanSubType,
+    /// 24 bit remote id
+    pub id: RemoteId,
+    /// Command, the meaning depends on the device type
+    pub command: u8,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Fan {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 5)?;
+        let sub_type = header.parse_sub_type()?;
+        let id = RemoteId::from((data[0] as u32) << 16 | (data[1] as u32) << 8 | data[2] as u32);
+
+        Ok(Self {
+            sub_type,
+            id,
+            command: data[3],
+            rssi: Rssi::from(data[4] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Curtain commands
+pub enum CurtainCommand {
+    /// Open
+    Open = 0x00,
+    /// Close
+    Close = 0x01,
+    /// Stop
+    Stop = 0x02,
+    /// Program
+    Program = 0x03,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Curtain message, used by Harrison curtain remotes
+pub struct Curtain {
+    /// Sub type
+    pub sub_type: u8,
+    /// House code, 'A' - 'P'
+    pub house_code: HouseCode,
+    /// Unit code, 1 - 16
+    pub unit_code: UnitCode,
+    /// Received command
+    pub command: CurtainCommand,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Curtain {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 4)?;
+        let command = header.parse_command(data[2])?;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            house_code: HouseCode::new(data[0] as char)?,
+            unit_code: UnitCode::new(data[1])?,
+            command,
+            rssi: Rssi::from(data[3] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// HomeConfort commands
+pub enum HomeConfortCommand {
+    /// Off
+    Off = 0x00,
+    /// On
+    On = 0x01,
+    /// Group off
+    GroupOff = 0x02,
+    /// Group on
+    GroupOn = 0x03,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// HomeConfort message, used by TEL-010 remotes
+pub struct HomeConfort {
+    /// Sub type
+    pub sub_type: u8,
+    /// 19 bit remote id
+    pub id: RemoteId,
+    /// House code, 'A' - 'D'
+    pub house_code: HouseCode,
+    /// Unit code, 1 - 4
+    pub unit_code: UnitCode,
+    /// Received command
+    pub command: HomeConfortCommand,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl HomeConfort {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 9)?;
+        let id =
+            RemoteId::from((data[0] as u32 & 0x07) << 16 | (data[1] as u32) << 8 | data[2] as u32);
+        let command = header.parse_command(data[5])?;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            house_code: HouseCode::new(data[3] as char)?,
+            unit_code: UnitCode::new(data[4])?,
+            command,
+            rssi: Rssi::from(data[8] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Device types using the Funkbus packet
+pub enum FunkbusSubType {
+    /// Gira remote
+    Gira = 0x00,
+    /// Insta remote
+    Insta = 0x01,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Funkbus commands
+pub enum FunkbusCommand {
+    /// Channel minus, off or down
+    ChannelMinus = 0x00,
+    /// Channel plus, on or up
+    ChannelPlus = 0x01,
+    /// All off
+    AllOff = 0x02,
+    /// All on
+    AllOn = 0x03,
+    /// Scene
+    Scene = 0x04,
+    /// Master minus
+    MasterMinus = 0x05,
+    /// Master plus
+    MasterPlus = 0x06,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Funkbus message, used by Gira and Insta remotes
+pub struct Funkbus {
+    /// Device type
+    pub sub_type: FunkbusSubType,
+    /// 16 bit device id
+    pub id: RemoteId,
+    /// Group, 'A' - 'C'
+    pub group: HouseCode,
+    /// Target button or scene number
+    pub unit_code: UnitCode,
+    /// Received command
+    pub command: FunkbusCommand,
+    /// How long the button was held, 0 for a short press
+    pub command_time: u8,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Funkbus {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 8)?;
+        let sub_type = header.parse_sub_type()?;
+        let command = header.parse_command(data[4])?;
+
+        Ok(Self {
+            sub_type,
+            id: RemoteId::from((data[0] as u32) << 8 | data[1] as u32),
+            group: HouseCode::new(data[2] as char)?,
+            unit_code: UnitCode::new(data[3])?,
+            command,
+            command_time: data[5],
+            rssi: Rssi::from(data[7] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Hunter fan message
+pub struct HunterFan {
+    /// Sub type
+    pub sub_type: u8,
+    /// 6 byte remote id
+    pub id: [u8; 6],
+    /// Command
+    pub command: u8,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl HunterFan {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 8)?;
+        let mut id = [0; 6];
+        id.copy_from_slice(&data[0..6]);
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            command: data[6],
+            rssi: Rssi::from(data[7] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Device types using the Security1 packet
+pub enum Security1SubType {
+    /// X10 security door/window sensor
+    X10 = 0x00,
+    /// X10 security motion sensor
+    X10Motion = 0x01,
+    /// X10 security remote
+    X10Remote = 0x02,
+    /// KD101 smoke detector
+    Kd101 = 0x03,
+    /// Visonic PowerCode door/window sensor
+    PowercodeSensor = 0x04,
+    /// Visonic PowerCode motion sensor
+    PowercodeMotion = 0x05,
+    /// Visonic CodeSecure
+    Codesecure = 0x06,
+    /// Visonic PowerCode door/window sensor, auxiliary contact
+    PowercodeAux = 0x07,
+    /// Meiantech
+    Meiantech = 0x08,
+    /// SA30 smoke detector
+    Sa30 = 0x09,
+    /// RM174RF smoke detector
+    Rm174rf = 0x0A,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Security1 status
+pub enum Security1Status {
+    /// Normal
+    Normal = 0x00,
+    /// Normal, delayed
+    NormalDelayed = 0x01,
+    /// Alarm
+    Alarm = 0x02,
+    /// Alarm, delayed
+    AlarmDelayed = 0x03,
+    /// Motion detected
+    Motion = 0x04,
+    /// No motion
+    NoMotion = 0x05,
+    /// Panic
+    Panic = 0x06,
+    /// End panic
+    PanicOff = 0x07,
+    /// IR beam
+    IrBeam = 0x08,
+    /// Arm away
+    ArmAway = 0x09,
+    /// Arm away, delayed
+    ArmAwayDelayed = 0x0A,
+    /// Arm home
+    ArmHome = 0x0B,
+    /// Arm home, delayed
+    ArmHomeDelayed = 0x0C,
+    /// Disarm
+    Disarm = 0x0D,
+    /// Light 1 off
+    LightOff = 0x10,
+    /// Light 1 on
+    LightOn = 0x11,
+    /// Light 2 off
+    Light2Off = 0x12,
+    /// Light 2 on
+    Light2On = 0x13,
+    /// Dark detected
+    Dark = 0x14,
+    /// Light detected
+    Light = 0x15,
+    /// Battery low
+    BatteryLow = 0x16,
+    /// Pair KD101
+    PairKd101 = 0x17,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Security1 message, used by door/window sensors, motion sensors and keyfobs
+pub struct Security1 {
+    /// Device type
+    pub sub_type: Security1SubType,
+    /// 24 bit device id
+    pub id: RemoteId,
+    /// Reported status
+    pub status: Security1Status,
+    /// Set if the sensor reports that it has been tampered with
+    pub tamper: bool,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Security1 {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 5)?;
+        let sub_type = header.parse_sub_type()?;
+        let id = RemoteId::from((data[0] as u32) << 16 | (data[1] as u32) << 8 | data[2] as u32);
+        // The top bit of the status signals tamper
+        let status = header.parse_command(data[3] & 0x7f)?;
+
+        Ok(Self {
+            sub_type,
+            id,
+            status,
+            tamper: data[3] & 0x80 != 0,
+            battery: data[4].into(),
+            rssi: Rssi::from(data[4] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Security2 message, used by KeeLoq classic rolling code keyfobs
+pub struct Security2 {
+    /// Sub type
+    pub sub_type: u8,
+    /// Encrypted, rolling part of the code
+    pub hopping_code: u32,
+    /// 28 bit serial number of the keyfob
+    pub id: RemoteId,
+    /// Bitmap of the pressed buttons
+    pub buttons: u8,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Security2 {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 25)?;
+        let hopping_code = (data[0] as u32) << 24
+            | (data[1] as u32) << 16
+            | (data[2] as u32) << 8
+            | data[3] as u32;
+        // The fixed part holds the buttons in the top nibble followed by the serial
+        let fixed = (data[4] as u32) << 24
+            | (data[5] as u32) << 16
+            | (data[6] as u32) << 8
+            | data[7] as u32;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            hopping_code,
+            id: RemoteId::from(fixed & 0x0fff_ffff),
+            buttons: (fixed >> 28) as u8,
+            battery: data[24].into(),
+            rssi: Rssi::from(data[24] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Camera pan/tilt commands
+pub enum CameraCommand {
+    /// Pan left
+    Left = 0x00,
+    /// Pan right
+    Right = 0x01,
+    /// Tilt up
+    Up = 0x02,
+    /// Tilt down
+    Down = 0x03,
+    /// Go to position 1
+    Position1 = 0x04,
+    /// Program position 1
+    ProgramPosition1 = 0x05,
+    /// Go to position 2
+    Position2 = 0x06,
+    /// Program position 2
+    ProgramPosition2 = 0x07,
+    /// Go to position 3
+    Position3 = 0x08,
+    /// Program position 3
+    ProgramPosition3 = 0x09,
+    /// Go to position 4
+    Position4 = 0x0A,
+    /// Program position 4
+    ProgramPosition4 = 0x0B,
+    /// Go to center position
+    Center = 0x0C,
+    /// Program center position
+    ProgramCenter = 0x0D,
+    /// Sweep
+    Sweep = 0x0E,
+    /// Program sweep
+    ProgramSweep = 0x0F,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Camera message, used by X10 Ninja pan/tilt remotes
+pub struct Camera {
+    /// Sub type
+    pub sub_type: u8,
+    /// House code, 'A' - 'P'
+    pub house_code: HouseCode,
+    /// Received command
+    pub command: CameraCommand,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Camera {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 3)?;
+        let command = header.parse_command(data[1])?;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            house_code: HouseCode::new(data[0] as char)?,
+            command,
+            rssi: Rssi::from(data[2] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Device types using the Remote packet
+pub enum RemoteSubType {
+    /// ATI Remote Wonder
+    Ati = 0x00,
+    /// ATI Remote Wonder Plus
+    AtiPlus = 0x01,
+    /// Medion
+    Medion = 0x02,
+    /// X10 PC Remote
+    PcRemote = 0x03,
+    /// ATI Remote Wonder II
+    AtiRemoteWonder2 = 0x04,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Remote control message, used by ATI, Medion and X10 PC remotes
+pub struct RemoteControl {
+    /// Device type
+    pub sub_type: RemoteSubType,
+    /// 8 bit remote id
+    pub id: RemoteId,
+    /// Pressed button
+    pub command: u8,
+    /// Alternates between presses, used to detect a held down button
+    pub toggle: bool,
+    /// Command type, only used by the ATI Remote Wonder II
+    pub command_type: u8,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl RemoteControl {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 3)?;
+        let sub_type = header.parse_sub_type()?;
+
+        Ok(Self {
+            sub_type,
+            id: RemoteId::from(data[0] as u32),
+            command: data[1],
+            toggle: data[2] & 0x08 != 0,
+            command_type: data[2] & 0x07,
+            rssi: Rssi::from(data[2] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// BBQ thermometer message, used by Maverick ET-732 style dual probe thermometers
+pub struct Bbq {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Food probe temperature in Celsius
+    pub food_temp: f32,
+    /// BBQ probe temperature in Celsius
+    pub bbq_temp: f32,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Bbq {
+    /// Food probe temperature in Fahrenheit, converted from `food_temp` in Celsius
+    pub fn food_temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.food_temp)
+    }
+
+    /// BBQ probe temperature in Fahrenheit, converted from `bbq_temp` in Celsius
+    pub fn bbq_temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.bbq_temp)
+    }
+
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 7)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let food_temp = ((data[2] as u16) << 8 | data[3] as u16) as f32;
+        let bbq_temp = ((data[4] as u16) << 8 | data[5] as u16) as f32;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            food_temp,
+            bbq_temp,
+            battery: data[6].into(),
+            rssi: Rssi::from(data[6] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Combined temperature and rain sensor message, used by WS1200
+pub struct TempRain {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Temperature in Celsius
+    pub temp: f32,
+    /// Total rain in mm
+    pub rain_total: f32,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl TempRain {
+    /// Temperature in Fahrenheit, converted from `temp` in Celsius
+    pub fn temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.temp)
+    }
+
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 7)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let temp = parse_temp(data[2], data[3]);
+        let rain_total = ((data[4] as u16) << 8 | data[5] as u16) as f32 / 10.0;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            temp,
+            rain_total,
+            battery: data[6].into(),
+            rssi: Rssi::from(data[6] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Temperature message, used by THR128, THC238 and similar sensors
+pub struct Temp {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Temperature in Celsius
+    pub temp: f32,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Temp {
+    /// Temperature in Fahrenheit, converted from `temp` in Celsius
+    pub fn temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.temp)
+    }
+
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 5)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let temp = parse_temp(data[2], data[3]);
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            temp,
+            battery: data[4].into(),
+            rssi: Rssi::from(data[4] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Humidity message, used by LaCrosse TX3 and similar sensors
+pub struct Hum {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Relative humidity in percent
+    pub humidity: u8,
+    /// Humidity status
+    pub humidity_status: HumidityStatus,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Hum {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 5)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            humidity: data[2],
+            humidity_status: data[3].into(),
+            battery: data[4].into(),
+            rssi: Rssi::from(data[4] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Weather forecast reported by weather stations
+pub enum Forecast {
+    /// No forecast available
+    NoForecast,
+    /// Sunny
+    Sunny,
+    /// Partly cloudy
+    PartlyCloudy,
+    /// Cloudy
+    Cloudy,
+    /// Rain
+    Rain,
+    /// A forecast this library doesn't know, with its value
+    Unknown(u8),
+}
+
+impl From<u8> for Forecast {
+    fn from(forecast: u8) -> Self {
+        match forecast {
+            0x00 => Forecast::NoForecast,
+            0x01 => Forecast::Sunny,
+            0x02 => Forecast::PartlyCloudy,
+            0x03 => Forecast::Cloudy,
+            0x04 => Forecast::Rain,
+            forecast => Forecast::Unknown(forecast),
+        }
+    }
+}
+
+impl From<Forecast> for u8 {
+    fn from(forecast: Forecast) -> u8 {
+        match forecast {
+            Forecast::NoForecast => 0x00,
+            Forecast::Sunny => 0x01,
+            Forecast::PartlyCloudy => 0x02,
+            Forecast::Cloudy => 0x03,
+            Forecast::Rain => 0x04,
+            Forecast::Unknown(forecast) => forecast,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Temperature, humidity and barometer message, used by BTHR918 and similar stations
+pub struct TempHumBaro {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Temperature in Celsius
+    pub temp: f32,
+    /// Relative humidity in percent
+    pub humidity: u8,
+    /// Humidity status
+    pub humidity_status: HumidityStatus,
+    /// Barometric pressure in hPa
+    pub baro: u16,
+    /// Weather forecast
+    pub forecast: Forecast,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl TempHumBaro {
+    /// Temperature in Fahrenheit, converted from `temp` in Celsius
+    pub fn temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.temp)
+    }
+
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 10)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let temp = parse_temp(data[2], data[3]);
+        let baro = (data[6] as u16) << 8 | data[7] as u16;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            temp,
+            humidity: data[4],
+            humidity_status: data[5].into(),
+            baro,
+            forecast: data[8].into(),
+            battery: data[9].into(),
+            rssi: Rssi::from(data[9] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Energy usage message, used by OWL CM119, CM160 and CM180
+pub struct Energy {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Packet counter
+    pub count: u8,
+    /// Instantaneous power in W
+    pub instant_power: u32,
+    /// Total usage in kWh
+    pub total_usage: f64,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Energy {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 14)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let instant_power = data[3..7].iter().fold(0u32, |acc, b| acc << 8 | *b as u32);
+        // The total is reported in units of 1/223.666 Wh
+        let total = data[7..13].iter().fold(0u64, |acc, b| acc << 8 | *b as u64);
+        let total_usage = total as f64 / 223.666 / 1000.0;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            count: data[2],
+            instant_power,
+            total_usage,
+            battery: data[13].into(),
+            rssi: Rssi::from(data[13] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Power meter message, used by Revolt ELEC5 meters
+pub struct Power {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Voltage in V
+    pub voltage: u8,
+    /// Current in A
+    pub current: f32,
+    /// Instantaneous power in W
+    pub power: f32,
+    /// Total energy in kWh
+    pub energy: f32,
+    /// Power factor, 0 - 1
+    pub power_factor: f32,
+    /// Frequency in Hz
+    pub frequency: u8,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Power {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 12)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let current = ((data[3] as u16) << 8 | data[4] as u16) as f32 / 100.0;
+        let power = ((data[5] as u16) << 8 | data[6] as u16) as f32 / 10.0;
+        let energy = ((data[7] as u16) << 8 | data[8] as u16) as f32 / 100.0;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            voltage: data[2],
+            current,
+            power,
+            energy,
+            power_factor: data[9] as f32 / 100.0,
+            frequency: data[10],
+            rssi: Rssi::from(data[11] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Weight message, used by BWR101, BWR102 and GR101 scales
+pub struct Weight {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Weight in kg
+    pub weight: f32,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl Weight {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 5)?;
+        let id = SensorId::from((data[0] as u16) << 8 | data[1] as u16);
+        let weight = ((data[2] as u16) << 8 | data[3] as u16) as f32 / 10.0;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id,
+            weight,
+            rssi: Rssi::from(data[4] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Async port mode
+pub enum AsyncPortCommand {
+    /// Port disabled
+    Disable = 0x00,
+    /// Receive P1 smart meter telegrams
+    ReceiveP1 = 0x01,
+    /// Receive Teleinfo
+    ReceiveTeleinfo = 0x02,
+    /// Receive raw bytes
+    ReceiveRaw = 0xFE,
+    /// Request the current settings
+    GetSettings = 0xFF,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Async port baud rate
+pub enum AsyncBaudRate {
+    /// 110 baud
+    Baud110 = 0x00,
+    /// 300 baud
+    Baud300 = 0x01,
+    /// 600 baud
+    Baud600 = 0x02,
+    /// 1200 baud
+    Baud1200 = 0x03,
+    /// 2400 baud
+    Baud2400 = 0x04,
+    /// 4800 baud
+    Baud4800 = 0x05,
+    /// 9600 baud
+    Baud9600 = 0x06,
+    /// 14400 baud
+    Baud14400 = 0x07,
+    /// 19200 baud
+    Baud19200 = 0x08,
+    /// 38400 baud
+    Baud38400 = 0x09,
+    /// 57600 baud
+    Baud57600 = 0x0A,
+    /// 115200 baud
+    Baud115200 = 0x0B,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Async port parity
+pub enum AsyncParity {
+    /// No parity
+    None = 0x00,
+    /// Odd parity
+    Odd = 0x01,
+    /// Even parity
+    Even = 0x02,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Async port configuration, reported by the RFXtrx433XL
+pub struct AsyncPort {
+    /// Port mode
+    pub command: AsyncPortCommand,
+    /// Baud rate
+    pub baud_rate: AsyncBaudRate,
+    /// Parity
+    pub parity: AsyncParity,
+    /// Number of data bits, 7 or 8
+    pub data_bits: u8,
+    /// Number of stop bits, 1 or 2
+    pub stop_bits: u8,
+    /// Inverted signal polarity
+    pub inverted: bool,
+}
+
+impl AsyncPort {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 6)?;
+        let command = header.parse_command(data[0])?;
+        let baud_rate = header.parse_value("baud rate", data[1])?;
+        let parity = header.parse_value("parity", data[2])?;
+
+        Ok(Self {
+            command,
+            baud_rate,
+            parity,
+            data_bits: data[3],
+            stop_bits: data[4],
+            inverted: data[5] != 0,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+/// Kind of data received on the async port
+pub enum AsyncDataSubType {
+    /// P1 smart meter telegram data
+    P1 = 0x01,
+    /// Teleinfo data
+    Teleinfo = 0x02,
+    /// Raw data
+    Raw = 0x03,
+    /// The receive buffer overflowed and data was lost
+    Overrun = 0xFE,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Data received on the RFXtrx433XL async port.
+///
+/// Longer telegrams are split over several frames with consecutive sequence
+/// numbers, use [`AsyncData::follows`] to detect lost frames while joining them.
+pub struct AsyncData {
+    /// Kind of data
+    pub sub_type: AsyncDataSubType,
+    /// Frame sequence number
+    pub seqnbr: u8,
+    /// Received bytes
+    pub data: Vec<u8>,
+}
+
+impl AsyncData {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        let sub_type = header.parse_sub_type()?;
+
+        Ok(Self {
+            sub_type,
+            seqnbr: header.seqnbr,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Returns true if this frame directly follows `previous`
+    pub fn follows(&self, previous: &AsyncData) -> bool {
+        self.sub_type == previous.sub_type && self.seqnbr == previous.seqnbr.wrapping_add(1)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Combined weather station message, used by Alecto WS1200, WH2900 and similar stations
+pub struct WeatherStation {
+    /// Sub type, the sensor model
+    pub sub_type: u8,
+    /// Sensor id
+    pub id: SensorId,
+    /// Wind direction in degrees
+    pub wind_direction: u16,
+    /// Average wind speed in m/s
+    pub wind_speed: f32,
+    /// Wind gust in m/s
+    pub wind_gust: f32,
+    /// Temperature in Celsius
+    pub temp: f32,
+    /// Wind chill in Celsius
+    pub chill: f32,
+    /// Relative humidity in percent
+    pub humidity: u8,
+    /// Humidity status
+    pub humidity_status: HumidityStatus,
+    /// Rain rate in mm/h
+    pub rain_rate: f32,
+    /// Total rain in mm
+    pub rain_total: f32,
+    /// UV index
+    pub uv: f32,
+    /// Solar radiation in W/m²
+    pub solar: u16,
+    /// Barometric pressure in hPa
+    pub baro: u16,
+    /// Weather forecast
+    pub forecast: Forecast,
+    /// Battery status
+    pub battery: BatteryStatus,
+    /// Signal strength
+    pub rssi: Rssi,
+}
+
+impl WeatherStation {
+    /// Temperature in Fahrenheit, converted from `temp` in Celsius
+    pub fn temp_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.temp)
+    }
+
+    /// Wind chill in Fahrenheit, converted from `chill` in Celsius
+    pub fn chill_fahrenheit(&self) -> f32 {
+        celsius_to_fahrenheit(self.chill)
+    }
+
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        check_len(data, 28)?;
+        let u16_at = |i: usize| (data[i] as u16) << 8 | data[i + 1] as u16;
+        let rain_total =
+            ((data[16] as u32) << 16 | (data[17] as u32) << 8 | data[18] as u32) as f32 / 10.0;
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            id: SensorId::from(u16_at(0)),
+            wind_direction: u16_at(2),
+            wind_speed: u16_at(4) as f32 / 10.0,
+            wind_gust: u16_at(6) as f32 / 10.0,
+            temp: parse_temp(data[8], data[9]),
+            chill: parse_temp(data[10], data[11]),
+            humidity: data[12],
+            humidity_status: data[13].into(),
+            rain_rate: u16_at(14) as f32 / 100.0,
+            rain_total,
+            uv: data[19] as f32 / 10.0,
+            solar: u16_at(20),
+            baro: u16_at(22),
+            forecast: data[24].into(),
+            battery: data[27].into(),
+            rssi: Rssi::from(data[27] >> 4),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Raw pulse train, received when the receiver runs in RAW mode
+pub struct RawPulses {
+    /// Frame number of the pulse train
+    pub sub_type: u8,
+    /// Alternating pulse and gap durations in microseconds
+    pub pulses: Vec<u16>,
+}
+
+impl RawPulses {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        let pulses = data
+            .chunks_exact(2)
+            .map(|p| (p[0] as u16) << 8 | p[1] as u16)
+            .collect();
+
+        Ok(Self {
+            sub_type: header.sub_type,
+            pulses,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Protocol family of an undecoded message
+pub enum UndecodedProtocol {
+    /// AC, KlikAanKlikUit, HomeEasy EU
+    Ac,
+    /// ARC
+    Arc,
+    /// ATI remotes
+    Ati,
+    /// Hideki, TFA, Cresta, UPM
+    Hideki,
+    /// LaCrosse
+    LaCrosse,
+    /// AD LightwaveRF
+    LightwaveRf,
+    /// Mertik Maxitrol
+    Mertik,
+    /// Oregon Scientific V1
+    Oregon1,
+    /// Oregon Scientific V2
+    Oregon2,
+    /// Oregon Scientific V3
+    Oregon3,
+    /// Proguard
+    Proguard,
+    /// Visonic PowerCode
+    Visonic,
+    /// NEC
+    Nec,
+    /// FS20
+    Fs20,
+    /// RSL, Revolt
+    Rsl,
+    /// Blinds
+    Blinds,
+    /// Rubicson
+    Rubicson,
+    /// AE Blyss
+    Ae,
+    /// Fineoffset, Viking
+    FineOffset,
+    /// RGB
+    Rgb,
+    /// Somfy RTS
+    Rts,
+    /// ByronSX, SelectPlus
+    SelectPlus,
+    /// HomeConfort
+    HomeConfort,
+    /// Edisio
+    Edisio,
+    /// Honeywell
+    Honeywell,
+    /// Funkbus
+    Funkbus,
+    /// A protocol this library doesn't know, with its sub type
+    Unknown(u8),
+}
+
+impl From<u8> for UndecodedProtocol {
+    /// From the sub type of the packet
+    fn from(sub_type: u8) -> Self {
+        match sub_type {
+            0x00 => UndecodedProtocol::Ac,
+            0x01 => UndecodedProtocol::Arc,
+            0x02 => UndecodedProtocol::Ati,
+            0x03 => UndecodedProtocol::Hideki,
+            0x04 => UndecodedProtocol::LaCrosse,
+            0x05 => UndecodedProtocol::LightwaveRf,
+            0x06 => UndecodedProtocol::Mertik,
+            0x07 => UndecodedProtocol::Oregon1,
+            0x08 => UndecodedProtocol::Oregon2,
+            0x09 => UndecodedProtocol::Oregon3,
+            0x0A => UndecodedProtocol::Proguard,
+            0x0B => UndecodedProtocol::Visonic,
+            0x0C => UndecodedProtocol::Nec,
+            0x0D => UndecodedProtocol::Fs20,
+            0x0E => UndecodedProtocol::Rsl,
+            0x0F => UndecodedProtocol::Blinds,
+            0x10 => UndecodedProtocol::Rubicson,
+            0x11 => UndecodedProtocol::Ae,
+            0x12 => UndecodedProtocol::FineOffset,
+            0x13 => UndecodedProtocol::Rgb,
+            0x14 => UndecodedProtocol::Rts,
+            0x15 => UndecodedProtocol::SelectPlus,
+            0x16 => UndecodedProtocol::HomeConfort,
+            0x17 => UndecodedProtocol::Edisio,
+            0x18 => UndecodedProtocol::Honeywell,
+            0x19 => UndecodedProtocol::Funkbus,
+            sub_type => UndecodedProtocol::Unknown(sub_type),
+        }
+    }
+}
+
+impl From<UndecodedProtocol> for u8 {
+    fn from(protocol: UndecodedProtocol) -> u8 {
+        match protocol {
+            UndecodedProtocol::Ac => 0x00,
+            UndecodedProtocol::Arc => 0x01,
+            UndecodedProtocol::Ati => 0x02,
+            UndecodedProtocol::Hideki => 0x03,
+            UndecodedProtocol::LaCrosse => 0x04,
+            UndecodedProtocol::LightwaveRf => 0x05,
+            UndecodedProtocol::Mertik => 0x06,
+            UndecodedProtocol::Oregon1 => 0x07,
+            UndecodedProtocol::Oregon2 => 0x08,
+            UndecodedProtocol::Oregon3 => 0x09,
+            UndecodedProtocol::Proguard => 0x0A,
+            UndecodedProtocol::Visonic => 0x0B,
+            UndecodedProtocol::Nec => 0x0C,
+            UndecodedProtocol::Fs20 => 0x0D,
+            UndecodedProtocol::Rsl => 0x0E,
+            UndecodedProtocol::Blinds => 0x0F,
+            UndecodedProtocol::Rubicson => 0x10,
+            UndecodedProtocol::Ae => 0x11,
+            UndecodedProtocol::FineOffset => 0x12,
+            UndecodedProtocol::Rgb => 0x13,
+            UndecodedProtocol::Rts => 0x14,
+            UndecodedProtocol::SelectPlus => 0x15,
+            UndecodedProtocol::HomeConfort => 0x16,
+            UndecodedProtocol::Edisio => 0x17,
+            UndecodedProtocol::Honeywell => 0x18,
+            UndecodedProtocol::Funkbus => 0x19,
+            UndecodedProtocol::Unknown(sub_type) => sub_type,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Message the receiver recognized the protocol of but couldn't decode.
+///
+/// Only sent when [`Protocols1::UNDECODED`] is enabled.
+pub struct Undecoded {
+    /// Protocol family
+    pub protocol: UndecodedProtocol,
+    /// Raw RF bytes
+    pub data: Vec<u8>,
+}
+
+impl Undecoded {
+    fn parse(header: PacketHeader, data: &[u8]) -> Result<Self> {
+        // Undecoded messages are for finding out what's on the air, keep the new ones
+        let protocol = UndecodedProtocol::from(header.sub_type);
+
+        Ok(Self {
+            protocol,
+            data: data.to_vec(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Lighting3 commands, used by Ikea Koppla
+pub enum Lighting3Command {
+    /// Bright
+    Bright = 0x00,
+    /// Dim
+    Dim = 0x08,
+    /// On
+    On = 0x10,
+    /// Dim level 1
+    Level1 = 0x11,
+    /// Dim level 2
+    Level2 = 0x12,
+    /// Dim level 3
+    Level3 = 0x13,
+    /// Dim level 4
+    Level4 = 0x14,
+    /// Dim level 5
+    Level5 = 0x15,
+    /// Dim level 6
+    Level6 = 0x16,
+    /// Dim level 7
+    Level7 = 0x17,
+    /// Dim level 8
+    Level8 = 0x18,
+    /// Dim level 9
+    Level9 = 0x19,
+    /// Off
+    Off = 0x1A,
+    /// Program
+    Program = 0x1C,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Device types using the Lighting5 packet
+pub enum Lighting5SubType {
+    /// LightwaveRF, Siemens
+    LightwaveRf = 0x00,
+    /// EMW100 GAO, Everflourish
+    Emw100 = 0x01,
+    /// BBSB new types
+    Bbsb = 0x02,
+    /// MDREMOTE LED dimmer
+    MdRemote = 0x03,
+    /// Conrad RSL2
+    Rsl = 0x04,
+    /// Livolo
+    Livolo = 0x05,
+    /// RGB TRC02
+    Trc02 = 0x06,
+    /// Aoke relay
+    Aoke = 0x07,
+    /// RGB TRC02_2
+    Trc02V2 = 0x08,
+    /// Eurodomest
+    Eurodomest = 0x09,
+    /// Livolo appliance 1 - 10
+    LivoloAppliance = 0x0A,
+    /// RGB432W
+    Rgb432w = 0x0B,
+    /// MDREMOTE 107 LED dimmer
+    MdRemote107 = 0x0C,
+    /// Legrand CAD
+    Legrand = 0x0D,
+    /// Avantek
+    Avantek = 0x0E,
+    /// IT, Intertek, FA500, PROmax
+    It = 0x0F,
+    /// MDREMOTE 108 LED dimmer
+    MdRemote108 = 0x10,
+    /// Kangtai, Cotech
+    Kangtai = 0x11,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Device types using the Lighting6 packet
+pub enum Lighting6SubType {
+    /// Blyss
+    Blyss = 0x00,
+    /// Cuveo
+    Cuveo = 0x01,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Lighting6 commands
+pub enum Lighting6Command {
+    /// On
+    On = 0x00,
+    /// Off
+    Off = 0x01,
+    /// Group on
+    GroupOn = 0x02,
+    /// Group off
+    GroupOff = 0x03,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Device types using the Chime packet
+pub enum ChimeSubType {
+    /// Byron SX
+    ByronSx = 0x00,
+    /// Byron MP001
+    ByronMp001 = 0x01,
+    /// SelectPlus
+    SelectPlus = 0x02,
+    /// SelectPlus3
+    SelectPlus3 = 0x03,
+    /// Envivo
+    Envivo = 0x04,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Device types using the RFY packet
+pub enum RfySubType {
+    /// Somfy RTS
+    Rfy = 0x00,
+    /// Somfy RTS, extended unit codes
+    RfyExt = 0x01,
+    /// ASA
+    Asa = 0x03,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// RFY commands
+pub enum RfyCommand {
+    /// Stop
+    Stop = 0x00,
+    /// Up
+    Up = 0x01,
+    /// Up and stop
+    UpStop = 0x02,
+    /// Down
+    Down = 0x03,
+    /// Down and stop
+    DownStop = 0x04,
+    /// Up and down
+    UpDown = 0x05,
+    /// List the remotes stored in the device
+    ListRemotes = 0x06,
+    /// Program, pairs the remote
+    Program = 0x07,
+    /// Program, held for 2 seconds
+    Program2Sec = 0x08,
+    /// Program, held for 7 seconds
+    Program7Sec = 0x09,
+    /// Stop, held for 2 seconds
+    Stop2Sec = 0x0A,
+    /// Stop, held for 5 seconds
+    Stop5Sec = 0x0B,
+    /// Up and down, held for 5 seconds
+    UpDown5Sec = 0x0C,
+    /// Erase this remote from the device
+    EraseThis = 0x0D,
+    /// Erase all remotes from the device
+    EraseAll = 0x0E,
+    /// Up, held for 0.5 seconds
+    Up05Sec = 0x0F,
+    /// Down, held for 0.5 seconds
+    Down05Sec = 0x10,
+    /// Up, held for 2 seconds
+    Up2Sec = 0x11,
+    /// Down, held for 2 seconds
+    Down2Sec = 0x12,
+    /// Enable the sun and wind detector
+    EnableSunWind = 0x13,
+    /// Disable the sun detector
+    DisableSun = 0x14,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Hunter fan commands
+pub enum HunterFanCommand {
+    /// Fan off
+    Off = 0x01,
+    /// Toggle the light
+    Light = 0x02,
+    /// Low speed
+    Low = 0x03,
+    /// Medium speed
+    Medium = 0x04,
+    /// High speed
+    High = 0x05,
+    /// Program
+    Program = 0x06,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Device types using the Thermostat1 packet
+pub enum Thermostat1SubType {
+    /// Digimax TLX7506
+    Digimax = 0x00,
+    /// Digimax with short format, no set point
+    DigimaxShort = 0x01,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Thermostat1 demand status
+pub enum Thermostat1Status {
+    /// No status available
+    NoStatus = 0x00,
+    /// Demand
+    Demand = 0x01,
+    /// No demand
+    NoDemand = 0x02,
+    /// Initializing
+    Initializing = 0x03,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Thermostat1 mode
+pub enum Thermostat1Mode {
+    /// Heating
+    Heating = 0x00,
+    /// Cooling
+    Cooling = 0x01,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Device types using the Thermostat3 packet
+pub enum Thermostat3SubType {
+    /// Mertik G6R-H4T1
+    MertikG6rH4t1 = 0x00,
+    /// Mertik G6R-H4TB
+    MertikG6rH4tb = 0x01,
+    /// Mertik G6R-H4TD
+    MertikG6rH4td = 0x02,
+    /// Mertik G6R-H4S
+    MertikG6rH4s = 0x03,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, FromPrimitive)]
+#[repr(u8)]
+/// Thermostat3 commands
+pub enum Thermostat3Command {
+    /// Off
+    Off = 0x00,
+    /// On
+    On = 0x01,
+    /// Up
+    Up = 0x02,
+    /// Down
+    Down = 0x03,
+    /// Run up, second off on the G6R-H4T1
+    RunUp = 0x04,
+    /// Run down, second on on the G6R-H4T1
+    RunDown = 0x05,
+    /// Stop
+    Stop = 0x06,
+}
+
+/// Returns an error if there are fewer than `expected` bytes of data
+fn check_len(data: &[u8], expected: usize) -> Result<()> {
+    if data.len() < expected {
+        return Err(TRXError::NotEnoughData {
+            received: data.len(),
+            expected,
+        });
+    }
+    Ok(())
+}
+
+/// Converts a temperature in Celsius to Fahrenheit
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// Parses a temperature in tenths of a degree, with the sign in the top bit
+fn parse_temp(high: u8, low: u8) -> f32 {
+    let temp = ((high & 0x7f) as i16) << 8 | low as i16;
+    let temp = if high & 0x80 != 0 { -temp } else { temp };
+    temp as f32 / 10.0
+}
+
+pub(crate) fn reset(seqnbr: SequenceNumber) -> Vec<u8> {
+    InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::Reset,
+        frequency: 0,
+        xmitpwr: 0,
+        extra: [0; 7],
+    }
+    .to_vec()
+}
+
+pub(crate) fn get_status(seqnbr: SequenceNumber) -> Vec<u8> {
+    InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::Status,
+        frequency: 0,
+        xmitpwr: 0,
+        extra: [0; 7],
+    }
+    .to_vec()
+}
+
+pub(crate) fn start_receiver(seqnbr: SequenceNumber) -> Vec<u8> {
+    InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::StartReceiver,
+        frequency: 0,
+        xmitpwr: 0,
+        extra: [0; 7],
+    }
+    .to_vec()
+}
+
+pub(crate) fn enable_all(seqnbr: SequenceNumber) -> Vec<u8> {
+    InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::EnableAll,
+        frequency: 0,
+        xmitpwr: 0,
+        extra: [0; 7],
+    }
+    .to_vec()
+}
+
+/// Returns true if `packet` is a set mode command
+pub(crate) fn is_set_mode(packet: &[u8]) -> bool {
+    packet.len() > 4
+        && packet[1] == PacketType::InterfaceControl as u8
+        && packet[2] == InterfaceControlSubType::InterfaceCommand as u8
+        && packet[4] == InterfaceCommandCmd::SetMode as u8
+}
+
+/// Highest receive noise level supported by the ProXL
+pub(crate) const NOISE_LEVEL_MAX: u8 = 0x0F;
+
+/// Gets the ProXL receive noise level, or sets it if `level` is given.
+/// The level is sent in the frequency byte and the second byte flags a write.
+pub(crate) fn noise_level(seqnbr: SequenceNumber, level: Option<u8>) -> Result<Vec<u8>> {
+    if let Some(level) = level {
+        if level > NOISE_LEVEL_MAX {
+            return Err(TRXError::InvalidArgument(format!(
+                "Noise level {} above {}",
+                level, NOISE_LEVEL_MAX
+            )));
+        }
+    }
+    Ok(InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::NoiseLevel,
+        frequency: level.unwrap_or(0),
+        xmitpwr: level.is_some() as u8,
+        extra: [0; 7],
+    }
+    .to_vec())
+}
+
+#[cfg(test)]
+pub(crate) fn parse_message(data: &[u8]) -> Result<ReceivedCommand> {
+    parse_packet(data).map(|(_, cmd)| cmd)
+}
+
+/// Parses a message, also returning its header
+pub(crate) fn parse_packet(data: &[u8]) -> Result<(PacketHeader, ReceivedCommand)> {
+    let (header, data) = PacketHeader::parse(data)?;
+    let cmd = parse_payload(header, data)?;
+    Ok((header, cmd))
+}
+
+fn parse_payload(header: PacketHeader, data: &[u8]) -> Result<ReceivedCommand> {
+    let interface_message = matches!(
+        header.packet_type,
+        PacketType::InterfaceMessage | PacketType::RecXmitMessage
+    );
+    match parse_known_payload(header, data) {
+        // Deliver the messages of sub types and commands added by newer firmware
+        Err(TRXError::UnknownSubPacketType { .. }) | Err(TRXError::UnknownCommand { .. })
+            if !interface_message =>
+        {
+            Ok(ReceivedCommand::ProtocolMessage(
+                ProtocolMessage::NotParsed {
+                    header,
+                    data: data.to_vec(),
+                },
+            ))
+        }
+        res => res,
+    }
+}
+
+fn parse_known_payload(header: PacketHeader, data: &[u8]) -> Result<ReceivedCommand> {
+    match header.packet_type {
+        PacketType::InterfaceMessage => Ok(ReceivedCommand::InterfaceMessage(
+            InterfaceMessage::parse(header, data)?,
+        )),
+        PacketType::RecXmitMessage => Ok(ReceivedCommand::InterfaceMessage(
+            InterfaceMessage::TransmitResponse(parse_transmit_response(header, data)?),
+        )),
+        PacketType::Undecoded => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::Undecoded(Undecoded::parse(header, data)?),
+        )),
+        PacketType::Lighting1 => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::Lighting1(Lighting1::parse(header, data)?),
+        )),
+        PacketType::Lighting2 => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::Lighting2(Lighting2::parse(header, data)?),
+        )),
+        PacketType::Lighting4 => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::Lighting4(Lighting4::parse(header, data)?),
+        )),
+        PacketType::Fan => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Fan(
+            Fan::parse(header, data)?,
+        ))),
+        PacketType::Curtain => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Curtain(
+            Curtain::parse(header, data)?,
+        ))),
+        PacketType::HomeConfort => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::HomeConfort(HomeConfort::parse(header, data)?),
+        )),
+        PacketType::Funkbus => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Funkbus(
+            Funkbus::parse(header, data)?,
+        ))),
+        PacketType::Hunter => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::HunterFan(HunterFan::parse(header, data)?),
+        )),
+        PacketType::Security1 => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::Security1(Security1::parse(header, data)?),
+        )),
+        PacketType::Security2 => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::Security2(Security2::parse(header, data)?),
+        )),
+        PacketType::Camera => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Camera(
+            Camera::parse(header, data)?,
+        ))),
+        PacketType::Remote => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::RemoteControl(RemoteControl::parse(header, data)?),
+        )),
+        PacketType::BBQ => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Bbq(
+            Bbq::parse(header, data)?,
+        ))),
+        PacketType::TempRain => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::TempRain(
+            TempRain::parse(header, data)?,
+        ))),
+        PacketType::TEMP => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Temp(
+            Temp::parse(header, data)?,
+        ))),
+        PacketType::HUM => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Hum(
+            Hum::parse(header, data)?,
+        ))),
+        PacketType::TempHum => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHum(
+            TempHum::parse(header, data)?,
+        ))),
+        PacketType::TempHumBaro => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::TempHumBaro(TempHumBaro::parse(header, data)?),
+        )),
+        PacketType::ENERGY => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Energy(
+            Energy::parse(header, data)?,
+        ))),
+        PacketType::POWER => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Power(
+            Power::parse(header, data)?,
+        ))),
+        PacketType::WEIGHT => Ok(ReceivedCommand::ProtocolMessage(ProtocolMessage::Weight(
+            Weight::parse(header, data)?,
+        ))),
+        PacketType::ASYNCPORT => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::AsyncPort(AsyncPort::parse(header, data)?),
+        )),
+        PacketType::ASYNCDATA => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::AsyncData(AsyncData::parse(header, data)?),
+        )),
+        PacketType::WEATHER => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::WeatherStation(WeatherStation::parse(header, data)?),
+        )),
+        PacketType::RAW => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::RawPulses(RawPulses::parse(header, data)?),
+        )),
+
+        // Catch all if we receive a command we don't know how to handle
+        _ => Ok(ReceivedCommand::ProtocolMessage(
+            ProtocolMessage::NotParsed {
+                header,
+                data: data.to_vec(),
+            },
+        )),
+    }
+}
+
+pub(crate) fn set_mode(
+    seqnbr: SequenceNumber,
+    frequency: Frequency,
+    xmitpwr: u8,
+    protos_1: Protocols1,
+    protos_2: Protocols2,
+    protos_3: Protocols3,
+    protos_4: Protocols4,
+) -> Vec<u8> {
+    set_mode_raw(
+        seqnbr,
+        frequency,
+        xmitpwr,
+        [
+            protos_1.bits(),
+            protos_2.bits(),
+            protos_3.bits(),
+            protos_4.bits(),
+        ],
+    )
+}
+
+pub(crate) fn set_mode_868(
+    seqnbr: SequenceNumber,
+    xmitpwr: u8,
+    protos_1: Protocols868_1,
+    protos_2: Protocols868_2,
+    protos_3: Protocols868_3,
+    protos_4: Protocols868_4,
+) -> Vec<u8> {
+    set_mode_raw(
+        seqnbr,
+        Frequency::TrxType868,
+        xmitpwr,
+        [
+            protos_1.bits(),
+            protos_2.bits(),
+            protos_3.bits(),
+            protos_4.bits(),
+        ],
+    )
+}
+
+/// Set mode with the protocol bytes as is, their meaning depends on the frequency
+pub(crate) fn set_mode_raw(
+    seqnbr: SequenceNumber,
+    frequency: Frequency,
+    xmitpwr: u8,
+    protos: [u8; 4],
+) -> Vec<u8> {
+    InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::SetMode,
+        frequency: frequency as u8,
+        xmitpwr,
+        extra: [protos[0], protos[1], protos[2], protos[3], 0, 0, 0],
+    }
+    .to_vec()
+}
+
+/// Lowest transmit power, in dBm
+const XMITPWR_MIN_DBM: i8 = -18;
+/// Highest transmit power on 433 MHz transceivers, in dBm
+const XMITPWR_MAX_DBM_433: i8 = 10;
+/// Highest transmit power on 868 MHz transceivers, in dBm
+const XMITPWR_MAX_DBM_868: i8 = 13;
+
+/// Converts a transmit power in dBm to the set mode xmitpwr value,
+/// validating it against what the hardware for `frequency` supports.
+pub(crate) fn transmit_power(frequency: Frequency, dbm: i8) -> Result<u8> {
+    let max = match frequency {
+        Frequency::TrxType868 => XMITPWR_MAX_DBM_868,
+        Frequency::TrxType310 | Frequency::TrxType315 | Frequency::TrxType43392 => {
+            XMITPWR_MAX_DBM_433
+        }
+        frequency => {
+            return Err(TRXError::InvalidArgument(format!(
+                "{:?} is a receiver, transmit power can't be set",
+                frequency
+            )))
+        }
+    };
+    if !(XMITPWR_MIN_DBM..=max).contains(&dbm) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Transmit power {} dBm outside {} - {} dBm",
+            dbm, XMITPWR_MIN_DBM, max
+        )));
+    }
+    Ok((dbm - XMITPWR_MIN_DBM) as u8)
+}
+
+pub(crate) fn save(seqnbr: SequenceNumber) -> Vec<u8> {
+    InterfaceCommand {
+        header: PacketHeader {
+            packet_type: PacketType::InterfaceControl,
+            sub_type: InterfaceControlSubType::InterfaceCommand as u8,
+            seqnbr,
+        },
+        cmd: InterfaceCommandCmd::Save,
+        frequency: 0,
+        xmitpwr: 0,
+        extra: [0; 7],
+    }
+    .to_vec()
+}
+
+/// Builds a packet from a header and payload, filling in the length byte
+fn build_packet(
+    packet_type: PacketType,
+    sub_type: u8,
+    seqnbr: SequenceNumber,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut v = Vec::with_capacity(payload.len() + 4);
+    PacketHeader {
+        packet_type,
+        sub_type,
+        seqnbr,
+    }
+    .extend(&mut v);
+    v.extend_from_slice(payload);
+    v[0] = (v.len() - 1) as u8;
+    v
+}
+
+/// Largest payload that fits in a packet, the length byte can't exceed 255
+const MAX_PAYLOAD_LEN: usize = 255 - 3;
+
+/// Builds a packet of any type, for commands that aren't modelled
+pub(crate) fn raw_packet(
+    seqnbr: SequenceNumber,
+    packet_type: u8,
+    sub_type: u8,
+    payload: &[u8],
+) -> Result<Vec<u8>> {
+    if payload.len() > MAX_PAYLOAD_LEN {
+        return Err(TRXError::InvalidArgument(format!(
+            "Payload of {} bytes longer than {}",
+            payload.len(),
+            MAX_PAYLOAD_LEN
+        )));
+    }
+    let mut v = Vec::with_capacity(payload.len() + 4);
+    v.extend_from_slice(&[(payload.len() + 3) as u8, packet_type, sub_type, seqnbr]);
+    v.extend_from_slice(payload);
+    Ok(v)
+}
+
+pub(crate) fn lighting1(
+    seqnbr: SequenceNumber,
+    sub_type: Lighting1SubType,
+    house_code: char,
+    unit_code: u8,
+    command: Lighting1Command,
+) -> Result<Vec<u8>> {
+    if !('A'..='P').contains(&house_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "House code {} not in A - P",
+            house_code
+        )));
+    }
+    if !(1..=16).contains(&unit_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Unit code {} not in 1 - 16",
+            unit_code
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Lighting1,
+        sub_type as u8,
+        seqnbr,
+        &[house_code as u8, unit_code, command as u8, 0],
+    ))
+}
+
+pub(crate) fn lighting2(
+    seqnbr: SequenceNumber,
+    sub_type: Lighting2SubType,
+    id: u32,
+    unit_code: u8,
+    command: Lighting2Command,
+    level: u8,
+) -> Result<Vec<u8>> {
+    if id > 0x03ff_ffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 26 bits",
+            id
+        )));
+    }
+    if !(1..=16).contains(&unit_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Unit code {} not in 1 - 16",
+            unit_code
+        )));
+    }
+    if level > 15 {
+        return Err(TRXError::InvalidArgument(format!(
+            "Dim level {} not in 0 - 15",
+            level
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Lighting2,
+        sub_type as u8,
+        seqnbr,
+        &[
+            (id >> 24) as u8,
+            (id >> 16) as u8,
+            (id >> 8) as u8,
+            id as u8,
+            unit_code,
+            command as u8,
+            level,
+            0,
+        ],
+    ))
+}
+
+pub(crate) fn lighting3(
+    seqnbr: SequenceNumber,
+    system: u32,
+    channels: u16,
+    command: Lighting3Command,
+) -> Result<Vec<u8>> {
+    if !(1..=16).contains(&system) {
+        return Err(TRXError::InvalidArgument(format!(
+            "System {} not in 1 - 16",
+            system
+        )));
+    }
+    if channels == 0 || channels > 0x03ff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Channel bitmap {:#X} must select channels 1 - 10",
+            channels
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Lighting3,
+        0,
+        seqnbr,
+        &[
+            (system - 1) as u8,
+            channels as u8,
+            (channels >> 8) as u8,
+            command as u8,
+            0,
+        ],
+    ))
+}
+
+pub(crate) fn lighting5(
+    seqnbr: SequenceNumber,
+    sub_type: Lighting5SubType,
+    id: u32,
+    unit_code: u8,
+    command: u8,
+    level: u8,
+) -> Result<Vec<u8>> {
+    if id > 0x00ff_ffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 24 bits",
+            id
+        )));
+    }
+    if level > 0x1f {
+        return Err(TRXError::InvalidArgument(format!(
+            "Level {} not in 0 - 31",
+            level
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Lighting5,
+        sub_type as u8,
+        seqnbr,
+        &[
+            (id >> 16) as u8,
+            (id >> 8) as u8,
+            id as u8,
+            unit_code,
+            command,
+            level,
+            0,
+        ],
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn lighting6(
+    seqnbr: SequenceNumber,
+    sub_type: Lighting6SubType,
+    id: u32,
+    group_code: char,
+    unit_code: u8,
+    command: Lighting6Command,
+    cmnd_seqnbr: u8,
+    rolling_code: u8,
+) -> Result<Vec<u8>> {
+    if id > 0xffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 16 bits",
+            id
+        )));
+    }
+    if !('A'..='P').contains(&group_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Group code {} not in A - P",
+            group_code
+        )));
+    }
+    if !(1..=5).contains(&unit_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Unit code {} not in 1 - 5",
+            unit_code
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Lighting6,
+        sub_type as u8,
+        seqnbr,
+        &[
+            (id >> 8) as u8,
+            id as u8,
+            group_code as u8,
+            unit_code,
+            command as u8,
+            cmnd_seqnbr,
+            rolling_code,
+            0,
+        ],
+    ))
+}
+
+pub(crate) fn chime(
+    seqnbr: SequenceNumber,
+    sub_type: ChimeSubType,
+    id: u32,
+    sound: u8,
+) -> Result<Vec<u8>> {
+    if id > 0xffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 16 bits",
+            id
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Chime,
+        sub_type as u8,
+        seqnbr,
+        &[(id >> 8) as u8, id as u8, sound, 0],
+    ))
+}
+
+pub(crate) fn rfy(
+    seqnbr: SequenceNumber,
+    sub_type: RfySubType,
+    id: u32,
+    unit_code: u8,
+    command: RfyCommand,
+) -> Result<Vec<u8>> {
+    if id > 0x000f_ffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 20 bits",
+            id
+        )));
+    }
+    // Standard RFY remotes have 4 channels, 0 addresses all of them
+    let max_unit_code = match sub_type {
+        RfySubType::Rfy => 4,
+        RfySubType::RfyExt | RfySubType::Asa => 0x0f,
+    };
+    if unit_code > max_unit_code {
+        return Err(TRXError::InvalidArgument(format!(
+            "Unit code {} not in 0 - {} for {:?}",
+            unit_code, max_unit_code, sub_type
+        )));
+    }
+    Ok(build_packet(
+        PacketType::RFY,
+        sub_type as u8,
+        seqnbr,
+        &[
+            (id >> 16) as u8,
+            (id >> 8) as u8,
+            id as u8,
+            unit_code,
+            command as u8,
+            0,
+            0,
+            0,
+            0,
+        ],
+    ))
+}
+
+pub(crate) fn home_confort(
+    seqnbr: SequenceNumber,
+    id: u32,
+    house_code: char,
+    unit_code: u8,
+    command: HomeConfortCommand,
+) -> Result<Vec<u8>> {
+    if id > 0x0007_ffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 19 bits",
+            id
+        )));
+    }
+    if !('A'..='D').contains(&house_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "House code {} not in A - D",
+            house_code
+        )));
+    }
+    if !(1..=4).contains(&unit_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Unit code {} not in 1 - 4",
+            unit_code
+        )));
+    }
+    Ok(build_packet(
+        PacketType::HomeConfort,
+        0,
+        seqnbr,
+        &[
+            (id >> 16) as u8,
+            (id >> 8) as u8,
+            id as u8,
+            house_code as u8,
+            unit_code,
+            command as u8,
+            0,
+            0,
+            0,
+        ],
+    ))
+}
+
+pub(crate) fn hunter_fan(
+    seqnbr: SequenceNumber,
+    id: [u8; 6],
+    command: HunterFanCommand,
+) -> Vec<u8> {
+    let mut payload = id.to_vec();
+    payload.extend_from_slice(&[command as u8, 0]);
+    build_packet(PacketType::Hunter, 0, seqnbr, &payload)
+}
+
+pub(crate) fn security1(
+    seqnbr: SequenceNumber,
+    sub_type: Security1SubType,
+    id: u32,
+    status: Security1Status,
+) -> Result<Vec<u8>> {
+    if id > 0x00ff_ffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 24 bits",
+            id
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Security1,
+        sub_type as u8,
+        seqnbr,
+        &[(id >> 16) as u8, (id >> 8) as u8, id as u8, status as u8, 0],
+    ))
+}
+
+pub(crate) fn camera(
+    seqnbr: SequenceNumber,
+    house_code: char,
+    command: CameraCommand,
+) -> Result<Vec<u8>> {
+    if !('A'..='P').contains(&house_code) {
+        return Err(TRXError::InvalidArgument(format!(
+            "House code {} not in A - P",
+            house_code
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Camera,
+        0,
+        seqnbr,
+        &[house_code as u8, command as u8, 0],
+    ))
+}
+
+pub(crate) fn remote(
+    seqnbr: SequenceNumber,
+    sub_type: RemoteSubType,
+    id: u32,
+    command: u8,
+    toggle: bool,
+) -> Result<Vec<u8>> {
+    if id > 0xff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 8 bits",
+            id
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Remote,
+        sub_type as u8,
+        seqnbr,
+        &[id as u8, command, if toggle { 0x08 } else { 0 }],
+    ))
+}
+
+pub(crate) fn thermostat1(
+    seqnbr: SequenceNumber,
+    sub_type: Thermostat1SubType,
+    id: u32,
+    temperature: u8,
+    set_point: u8,
+    status: Thermostat1Status,
+    mode: Thermostat1Mode,
+) -> Result<Vec<u8>> {
+    if id > 0xffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Id {:#X} doesn't fit in 16 bits",
+            id
+        )));
+    }
+    if temperature > 50 {
+        return Err(TRXError::InvalidArgument(format!(
+            "Temperature {} not in 0 - 50",
+            temperature
+        )));
+    }
+    if !(5..=45).contains(&set_point) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Set point {} not in 5 - 45",
+            set_point
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Thermostat1,
+        sub_type as u8,
+        seqnbr,
+        &[
+            (id >> 8) as u8,
+            id as u8,
+            temperature,
+            set_point,
+            (mode as u8) << 7 | status as u8,
+            0,
+        ],
+    ))
+}
+
+pub(crate) fn thermostat3(
+    seqnbr: SequenceNumber,
+    sub_type: Thermostat3SubType,
+    unit_id: u32,
+    command: Thermostat3Command,
+) -> Result<Vec<u8>> {
+    if unit_id > 0x00ff_ffff {
+        return Err(TRXError::InvalidArgument(format!(
+            "Unit id {:#X} doesn't fit in 24 bits",
+            unit_id
+        )));
+    }
+    Ok(build_packet(
+        PacketType::Thermostat3,
+        sub_type as u8,
+        seqnbr,
+        &[
+            (unit_id >> 16) as u8,
+            (unit_id >> 8) as u8,
+            unit_id as u8,
+            command as u8,
+            0,
+        ],
+    ))
+}
+
+/// Max number of pulses that fit in one RAW frame
+const RAW_PULSES_PER_FRAME: usize = 124;
+/// Max number of RAW frames in one transmission
+const RAW_MAX_FRAMES: usize = 4;
+
+/// Splits a pulse train over the needed RAW frames, numbered from `seqnbr`.
+///
+/// The sub type holds the frame number and only the last frame carries the
+/// repeat count, which tells the device to start transmitting.
+pub(crate) fn raw_pulses(
+    seqnbr: SequenceNumber,
+    pulses: &[u16],
+    repeat: u8,
+) -> Result<Vec<Vec<u8>>> {
+    if pulses.is_empty() || pulses.len() > RAW_PULSES_PER_FRAME * RAW_MAX_FRAMES {
+        return Err(TRXError::InvalidArgument(format!(
+            "Number of pulses {} not in 1 - {}",
+            pulses.len(),
+            RAW_PULSES_PER_FRAME * RAW_MAX_FRAMES
+        )));
+    }
+    if repeat == 0 {
+        return Err(TRXError::InvalidArgument(
+            "Repeat count must be at least 1".to_string(),
+        ));
+    }
+    let chunks = pulses.chunks(RAW_PULSES_PER_FRAME);
+    let last = chunks.len() - 1;
+    let mut seqnbr = seqnbr;
+    Ok(chunks
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut payload = Vec::with_capacity(chunk.len() * 2 + 1);
+            payload.push(if i == last { repeat } else { 0 });
+            for pulse in chunk {
+                payload.extend_from_slice(&pulse.to_be_bytes());
+            }
+            let frame = build_packet(PacketType::RAW, i as u8, seqnbr, &payload);
+            seqnbr = following_seqnbr(seqnbr);
+            frame
+        })
+        .collect())
+}
+
+/// Sub type of the async port configuration packet
+const ASYNC_PORT_CONFIG: u8 = 0x01;
+/// Max number of data bytes in one async data frame
+const ASYNC_DATA_PER_FRAME: usize = 252;
+
+pub(crate) fn async_port(seqnbr: SequenceNumber, config: &AsyncPort) -> Result<Vec<u8>> {
+    if !(7..=8).contains(&config.data_bits) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Data bits {} not in 7 - 8",
+            config.data_bits
+        )));
+    }
+    if !(1..=2).contains(&config.stop_bits) {
+        return Err(TRXError::InvalidArgument(format!(
+            "Stop bits {} not in 1 - 2",
+            config.stop_bits
+        )));
+    }
+    Ok(build_packet(
+        PacketType::ASYNCPORT,
+        ASYNC_PORT_CONFIG,
+        seqnbr,
+        &[
+            config.command as u8,
+            config.baud_rate as u8,
+            config.parity as u8,
+            config.data_bits,
+            config.stop_bits,
+            config.inverted as u8,
+            0,
+            0,
+        ],
+    ))
+}
+
+/// Splits data over the needed async data frames, numbered from `seqnbr`
+pub(crate) fn async_data(seqnbr: SequenceNumber, data: &[u8]) -> Result<Vec<Vec<u8>>> {
+    if data.is_empty() {
+        return Err(TRXError::InvalidArgument("No data to send".to_string()));
+    }
+    let mut seqnbr = seqnbr;
+    Ok(data
+        .chunks(ASYNC_DATA_PER_FRAME)
+        .map(|chunk| {
+            let frame = build_packet(
+                PacketType::ASYNCDATA,
+                AsyncDataSubType::Raw as u8,
+                seqnbr,
+                chunk,
+            );
+            seqnbr = following_seqnbr(seqnbr);
+            frame
+        })
+        .collect())
+}
+
+/// Sequence number of the command after `seqnbr`, skipping the one reserved for
+/// internal commands
+pub(crate) fn following_seqnbr(seqnbr: SequenceNumber) -> SequenceNumber {
+    match seqnbr.wrapping_add(1) {
+        crate::INTERNAL_SEQNBR => 0,
+        next => next,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn create_reset() {
+        let cmd = reset(1);
+        let cmd = cmd.to_vec();
+        assert_eq!(vec![0xd, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], cmd);
+    }
+
+    #[test]
+    fn create_save_settings() {
+        let cmd = save(0x11).to_vec();
+        assert_eq!(vec![0x0d, 00, 00, 0x11, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0], cmd);
+    }
+
+    #[test]
+    fn parse_status() {
+        let msg = parse_message(&[
+            0x01, 0x00, 0x01, 0x02, 0x53, 0x0C, 0x08, 0x00, 0x27, 0x00, 0x01, 0x03, 0x1C, 0x04,
+            0x52, 0x46, 0x58, 0x43, 0x4F, 0x4D,
+        ])
+        .unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::Status {
+            frequency,
+            fw_version,
+            hw_version_major,
+            hw_version_minor,
+            output_power,
+            fw_type,
+            device_name,
+            ..
+        }) = msg
+        {
+            assert_eq!(Frequency::TrxType43392 as u8, frequency as u8);
+            assert_eq!(0x0C, fw_version);
+            assert_eq!(1, hw_version_major);
+            assert_eq!(3, hw_version_minor);
+            assert_eq!(0x1C, output_power);
+            assert_eq!(Some(FWType::TypeExt2), fw_type);
+            assert_eq!(Some("RFXCOM".to_string()), device_name);
+        } else {
+            panic!("Expected Status, got {:?}", msg);
+        }
+
+        // Older firmware without firmware type and name
+        let msg = parse_message(&[
+            0x01, 0x00, 0x01, 0x02, 0x53, 0x0C, 0x08, 0x00, 0x27, 0x00, 0x01, 0x03, 0x1C,
+        ])
+        .unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::Status {
+            fw_type,
+            device_name,
+            ..
+        }) = msg
+        {
+            assert_eq!(None, fw_type);
+            assert_eq!(None, device_name);
+        } else {
+            panic!("Expected Status, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_receiver_started() {
+        let mut data = vec![0x01, 0x07, 0x02, 0x07];
+        data.extend_from_slice(b"Copyright RFXCOM");
+        let msg = parse_message(&data).unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::ReceiverStarted(copyright)) = msg
+        {
+            assert_eq!("Copyright RFXCOM", copyright);
+        } else {
+            panic!("Expected ReceiverStarted, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn noise_level_cmd() {
+        assert_eq!(
+            vec![0x0d, 0x00, 0x00, 0x02, 0x0B, 0x05, 0x01, 0, 0, 0, 0, 0, 0, 0],
+            noise_level(2, Some(5)).unwrap()
+        );
+        assert_eq!(
+            vec![0x0d, 0x00, 0x00, 0x02, 0x0B, 0x00, 0x00, 0, 0, 0, 0, 0, 0, 0],
+            noise_level(2, None).unwrap()
+        );
+        assert!(noise_level(2, Some(0x10)).is_err());
+
+        let msg = parse_message(&[0x01, 0x00, 0x02, 0x0B, 0x05]).unwrap();
+        assert!(matches!(
+            msg,
+            ReceivedCommand::InterfaceMessage(InterfaceMessage::NoiseLevel(5))
+        ));
+    }
+
+    #[test]
+    fn parse_interface_errors() {
+        let msg = parse_message(&[0x01, 0x02, 0x03, 0x01]).unwrap();
+        assert!(matches!(
+            msg,
+            ReceivedCommand::InterfaceMessage(InterfaceMessage::ExtError(0x01))
+        ));
+        let msg = parse_message(&[0x01, 0xFF, 0x03, 0x0A]).unwrap();
+        assert!(matches!(
+            msg,
+            ReceivedCommand::InterfaceMessage(InterfaceMessage::WrongCommand(0x0A))
+        ));
+        assert!(matches!(
+            parse_message(&[0x01, 0x00, 0x03]),
+            Err(TRXError::NotEnoughData {
+                received: 0,
+                expected: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn create_enable_all() {
+        let cmd = enable_all(0x05);
+        assert_eq!(
+            vec![0x0d, 0x00, 0x00, 0x05, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            cmd
+        );
+    }
+
+    #[test]
+    fn set_mode_x10() {
+        // X10
+        let cmd = set_mode(
+            0x12,
+            Default::default(),
+            0,
+            Protocols1::empty(),
+            Protocols2::empty(),
+            Protocols3::X10,
+            Protocols4::empty(),
+        )
+        .to_vec();
+        assert_eq!(
+            vec![0x0d, 00, 00, 0x12, 03, 0x53, 00, 00, 00, 01, 00, 00, 00, 00],
+            cmd
+        );
+    }
+
+    #[test]
+    fn set_mode_mixed() {
+        let cmd = set_mode(
+            0x12,
+            Default::default(),
+            0,
+            Protocols1::IMAGINTRONIX | Protocols1::RUBICSON,
+            Protocols2::LEGRAND | Protocols2::MERTIK,
+            Protocols3::X10 | Protocols3::ATI,
+            Protocols4::KEELOQ,
+        )
+        .to_vec();
+        assert_eq!(
+            vec![0x0d, 00, 00, 0x12, 03, 0x53, 00, 0x42, 0x11, 0x41, 0x01, 00, 00, 00],
+            cmd
+        );
+    }
+
+    #[test]
+    fn set_mode_868_fs20() {
+        let cmd = set_mode_868(
+            0x12,
+            0,
+            Protocols868_1::LACROSSE,
+            Protocols868_2::FS20,
+            Protocols868_3::empty(),
+            Protocols868_4::PROOVE,
+        );
+        assert_eq!(
+            vec![
+                0x0d, 0x00, 0x00, 0x12, 0x03, 0x55, 0x00, 0x04, 0x20, 0x00, 0x04, 0x00, 0x00, 0x00
+            ],
+            cmd
+        );
+    }
+
+    #[test]
+    fn set_mode_transmit_power() {
+        let xmitpwr = transmit_power(Frequency::TrxType868, 13).unwrap();
+        let cmd = set_mode(
+            0x12,
+            Frequency::TrxType868,
+            xmitpwr,
+            Protocols1::empty(),
+            Protocols2::empty(),
+            Protocols3::empty(),
+            Protocols4::empty(),
+        );
+        assert_eq!(
+            vec![
+                0x0d, 0x00, 0x00, 0x12, 0x03, 0x55, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+            ],
+            cmd
+        );
+        assert_eq!(0, transmit_power(Frequency::TrxType43392, -18).unwrap());
+        assert!(transmit_power(Frequency::TrxType43392, 11).is_err());
+        assert!(transmit_power(Frequency::RecType43392, 0).is_err());
+    }
+
+    #[test]
+    fn set_mode_preset() {
+        let preset = ModePreset::weather_sensors() | ModePreset::security();
+        let cmd = set_mode(
+            0x12,
+            Default::default(),
+            0,
+            preset.protos_1,
+            preset.protos_2,
+            preset.protos_3,
+            preset.protos_4,
+        );
+        assert_eq!(
+            vec![
+                0x0d, 0x00, 0x00, 0x12, 0x03, 0x53, 0x00, 0x46, 0x0C, 0xB1, 0x01, 0x00, 0x00, 0x00
+            ],
+            cmd
+        );
+    }
+
+    #[test]
+    fn detect_set_mode() {
+        let cmd = set_mode_raw(1, Frequency::TrxType43392, 0, [0; 4]);
+        assert!(is_set_mode(&cmd));
+        assert!(!is_set_mode(&save(1)));
+        assert!(!is_set_mode(&[0x01]));
+    }
+
+    #[test]
+    fn save_cmd() {
+        let cmd = super::save(3).to_vec();
+        assert_eq!(vec![0x0d, 0, 0, 3, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0], cmd);
+    }
+
+    #[test]
+    fn parse_lighting1() {
+        let msg = parse_message(&[0x10, 0x01, 0x00, 0x42, 0x05, 0x01, 0x70]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Lighting1(msg)) = msg {
+            assert_eq!(Lighting1SubType::Arc, msg.sub_type);
+            assert_eq!('B', msg.house_code);
+            assert_eq!(5, msg.unit_code);
+            assert_eq!(Lighting1Command::On, msg.command);
+            assert_eq!(7, msg.rssi);
+        } else {
+            panic!("Expected Lighting1, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_lighting2() {
+        let msg = parse_message(&[
+            0x11, 0x00, 0x01, 0x01, 0x23, 0x45, 0x67, 0x0A, 0x02, 0x0F, 0x60,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Lighting2(msg)) = msg {
+            assert_eq!(Lighting2SubType::Ac, msg.sub_type);
+            assert_eq!(0x1234567, msg.id);
+            assert_eq!(10, msg.unit_code);
+            assert_eq!(Lighting2Command::SetLevel, msg.command);
+            assert_eq!(15, msg.level);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected Lighting2, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_lighting4() {
+        let msg = parse_message(&[0x13, 0x00, 0x04, 0x12, 0x34, 0x56, 0x01, 0x5E, 0x80]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Lighting4(msg)) = msg {
+            assert_eq!(0x123456, msg.code);
+            assert_eq!(350, msg.pulse);
+            assert_eq!(8, msg.rssi);
+        } else {
+            panic!("Expected Lighting4, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_fan() {
+        let msg = parse_message(&[0x17, 0x04, 0x00, 0x12, 0x34, 0x56, 0x03, 0x50]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Fan(msg)) = msg {
+            assert_eq!(FanSubType::Westinghouse, msg.sub_type);
+            assert_eq!(0x123456, msg.id);
+            assert_eq!(3, msg.command);
+            assert_eq!(5, msg.rssi);
+        } else {
+            panic!("Expected Fan, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_curtain() {
+        let msg = parse_message(&[0x18, 0x00, 0x00, 0x41, 0x02, 0x01, 0x40]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Curtain(msg)) = msg {
+            assert_eq!('A', msg.house_code);
+            assert_eq!(2, msg.unit_code);
+            assert_eq!(CurtainCommand::Close, msg.command);
+            assert_eq!(4, msg.rssi);
+        } else {
+            panic!("Expected Curtain, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_home_confort() {
+        let msg = parse_message(&[
+            0x1B, 0x00, 0x00, 0x06, 0x34, 0x56, 0x43, 0x04, 0x01, 0x00, 0x00, 0x70,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::HomeConfort(msg)) = msg {
+            assert_eq!(0x063456, msg.id);
+            assert_eq!('C', msg.house_code);
+            assert_eq!(4, msg.unit_code);
+            assert_eq!(HomeConfortCommand::On, msg.command);
+            assert_eq!(7, msg.rssi);
+        } else {
+            panic!("Expected HomeConfort, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_funkbus() {
+        let msg = parse_message(&[
+            0x1E, 0x01, 0x00, 0xAB, 0xCD, 0x42, 0x03, 0x01, 0x02, 0x00, 0x60,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Funkbus(msg)) = msg {
+            assert_eq!(FunkbusSubType::Insta, msg.sub_type);
+            assert_eq!(0xABCD, msg.id);
+            assert_eq!('B', msg.group);
+            assert_eq!(3, msg.unit_code);
+            assert_eq!(FunkbusCommand::ChannelPlus, msg.command);
+            assert_eq!(2, msg.command_time);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected Funkbus, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_hunter_fan() {
+        let msg = parse_message(&[
+            0x1F, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x02, 0x80,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::HunterFan(msg)) = msg {
+            assert_eq!([1, 2, 3, 4, 5, 6], msg.id);
+            assert_eq!(2, msg.command);
+            assert_eq!(8, msg.rssi);
+        } else {
+            panic!("Expected HunterFan, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_security1() {
+        let msg = parse_message(&[0x20, 0x05, 0x00, 0x12, 0x34, 0x56, 0x84, 0x59]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Security1(msg)) = msg {
+            assert_eq!(Security1SubType::PowercodeMotion, msg.sub_type);
+            assert_eq!(0x123456, msg.id);
+            assert_eq!(Security1Status::Motion, msg.status);
+            assert!(msg.tamper);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(5, msg.rssi);
+        } else {
+            panic!("Expected Security1, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_security2() {
+        let mut frame = vec![0x21, 0x00, 0x00];
+        frame.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF, 0x21, 0x23, 0x45, 0x67]);
+        frame.extend_from_slice(&[0; 16]);
+        frame.push(0x6F);
+        let msg = parse_message(&frame).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Security2(msg)) = msg {
+            assert_eq!(0xDEADBEEF, msg.hopping_code);
+            assert_eq!(0x1234567, msg.id);
+            assert_eq!(2, msg.buttons);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected Security2, got {:?}", msg);
+        }
+    }
 
     #[test]
-    fn set_mode_x10() {
-        // X10
-        let cmd = set_mode(
-            0x12,
-            Default::default(),
-            Protocols1::empty(),
-            Protocols2::empty(),
-            Protocols3::X10,
-            Protocols4::empty(),
+    fn parse_camera() {
+        let msg = parse_message(&[0x28, 0x00, 0x00, 0x44, 0x0E, 0x30]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Camera(msg)) = msg {
+            assert_eq!('D', msg.house_code);
+            assert_eq!(CameraCommand::Sweep, msg.command);
+            assert_eq!(3, msg.rssi);
+        } else {
+            panic!("Expected Camera, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_remote_control() {
+        let msg = parse_message(&[0x30, 0x04, 0x00, 0x0F, 0x12, 0x5A]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::RemoteControl(msg)) = msg {
+            assert_eq!(RemoteSubType::AtiRemoteWonder2, msg.sub_type);
+            assert_eq!(0x0F, msg.id);
+            assert_eq!(0x12, msg.command);
+            assert!(msg.toggle);
+            assert_eq!(2, msg.command_type);
+            assert_eq!(5, msg.rssi);
+        } else {
+            panic!("Expected RemoteControl, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_bbq() {
+        let msg =
+            parse_message(&[0x4E, 0x01, 0x00, 0x12, 0x34, 0x00, 0x40, 0x00, 0xC8, 0x79]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Bbq(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(64.0, msg.food_temp);
+            assert_eq!(200.0, msg.bbq_temp);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(7, msg.rssi);
+        } else {
+            panic!("Expected Bbq, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_temp_rain() {
+        let msg =
+            parse_message(&[0x4F, 0x01, 0x00, 0x12, 0x34, 0x80, 0x2D, 0x01, 0x0B, 0x69]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::TempRain(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(-4.5, msg.temp);
+            assert_eq!(26.7, msg.rain_total);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected TempRain, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_temp() {
+        let msg = parse_message(&[0x50, 0x02, 0x00, 0x12, 0x34, 0x00, 0xD5, 0x69]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Temp(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(21.3, msg.temp);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected Temp, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_hum() {
+        let msg = parse_message(&[0x51, 0x01, 0x00, 0x12, 0x34, 0x2D, 0x01, 0x79]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Hum(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(45, msg.humidity);
+            assert_eq!(HumidityStatus::Comfort, msg.humidity_status);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(7, msg.rssi);
+        } else {
+            panic!("Expected Hum, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn temperature_in_fahrenheit() {
+        // -5.0 C
+        let msg = parse_message(&[0x50, 0x02, 0x00, 0x12, 0x34, 0x80, 0x32, 0x69]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(msg) = msg {
+            let temp = msg.temperature().unwrap();
+            assert!((temp + 5.0).abs() < 0.01);
+            assert!((TemperatureUnit::Fahrenheit.convert(temp) - 23.0).abs() < 0.01);
+            assert!((TemperatureUnit::Celsius.convert(temp) + 5.0).abs() < 0.01);
+        } else {
+            panic!("Expected protocol message, got {:?}", msg);
+        }
+        assert!((celsius_to_fahrenheit(100.0) - 212.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn parse_temp_hum_baro() {
+        let msg = parse_message(&[
+            0x54, 0x01, 0x00, 0x12, 0x34, 0x00, 0xD5, 0x2D, 0x01, 0x03, 0xF5, 0x02, 0x69,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHumBaro(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(21.3, msg.temp);
+            assert_eq!(45, msg.humidity);
+            assert_eq!(HumidityStatus::Comfort, msg.humidity_status);
+            assert_eq!(1013, msg.baro);
+            assert_eq!(Forecast::PartlyCloudy, msg.forecast);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected TempHumBaro, got {:?}", msg);
+        }
+
+        let msg = parse_message(&[
+            0x54, 0x01, 0x00, 0x12, 0x34, 0x00, 0xD5, 0x2D, 0x01, 0x03, 0xF5, 0x07, 0x69,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHumBaro(msg)) = msg {
+            assert_eq!(Forecast::Unknown(0x07), msg.forecast);
+            assert_eq!(0x07, u8::from(msg.forecast));
+        } else {
+            panic!("Expected TempHumBaro, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_energy() {
+        let msg = parse_message(&[
+            0x5A, 0x01, 0x00, 0x12, 0x34, 0x05, 0x00, 0x00, 0x01, 0xF4, 0x00, 0x00, 0x00, 0x03,
+            0x69, 0xB2, 0x79,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Energy(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(5, msg.count);
+            assert_eq!(500, msg.instant_power);
+            // 223666 units is exactly 1 kWh
+            assert!((msg.total_usage - 1.0).abs() < 1e-9);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(7, msg.rssi);
+        } else {
+            panic!("Expected Energy, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_power() {
+        let msg = parse_message(&[
+            0x5C, 0x01, 0x00, 0x12, 0x34, 0xE6, 0x00, 0x64, 0x08, 0xFC, 0x04, 0xD2, 0x5F, 0x32,
+            0x70,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Power(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(230, msg.voltage);
+            assert_eq!(1.0, msg.current);
+            assert_eq!(230.0, msg.power);
+            assert_eq!(12.34, msg.energy);
+            assert_eq!(0.95, msg.power_factor);
+            assert_eq!(50, msg.frequency);
+            assert_eq!(7, msg.rssi);
+        } else {
+            panic!("Expected Power, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_weight() {
+        let msg = parse_message(&[0x5D, 0x01, 0x00, 0x12, 0x34, 0x02, 0xEB, 0x50]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Weight(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(74.7, msg.weight);
+            assert_eq!(5, msg.rssi);
+        } else {
+            panic!("Expected Weight, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_async_port() {
+        let msg = parse_message(&[
+            0x61, 0x01, 0x00, 0x01, 0x0B, 0x00, 0x08, 0x01, 0x00, 0x00, 0x00,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::AsyncPort(msg)) = msg {
+            assert_eq!(AsyncPortCommand::ReceiveP1, msg.command);
+            assert_eq!(AsyncBaudRate::Baud115200, msg.baud_rate);
+            assert_eq!(AsyncParity::None, msg.parity);
+            assert_eq!(8, msg.data_bits);
+            assert_eq!(1, msg.stop_bits);
+            assert!(!msg.inverted);
+        } else {
+            panic!("Expected AsyncPort, got {:?}", msg);
+        }
+
+        let res = parse_message(&[
+            0x61, 0x01, 0x00, 0x01, 0x0B, 0x07, 0x08, 0x01, 0x00, 0x00, 0x00,
+        ]);
+        assert!(matches!(
+            res,
+            Err(TRXError::InvalidValue {
+                field: "parity",
+                value: 0x07,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_async_data() {
+        let first = parse_message(&[0x62, 0x01, 0xFF, b'/', b'X']).unwrap();
+        let second = parse_message(&[0x62, 0x01, 0x00, b'!']).unwrap();
+        match (first, second) {
+            (
+                ReceivedCommand::ProtocolMessage(ProtocolMessage::AsyncData(first)),
+                ReceivedCommand::ProtocolMessage(ProtocolMessage::AsyncData(second)),
+            ) => {
+                assert_eq!(AsyncDataSubType::P1, first.sub_type);
+                assert_eq!(b"/X".to_vec(), first.data);
+                assert_eq!(b"!".to_vec(), second.data);
+                assert!(second.follows(&first));
+                assert!(!first.follows(&second));
+            }
+            msgs => panic!("Expected AsyncData, got {:?}", msgs),
+        }
+    }
+
+    #[test]
+    fn parse_weather_station() {
+        let msg = parse_message(&[
+            0x76, 0x01, 0x00, 0x12, 0x34, 0x00, 0xB4, 0x00, 0x25, 0x00, 0x3C, 0x00, 0xD5, 0x00,
+            0xC8, 0x2D, 0x01, 0x01, 0x2C, 0x00, 0x01, 0x0B, 0x23, 0x02, 0x58, 0x03, 0xF5, 0x01,
+            0x00, 0x00, 0x69,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::WeatherStation(msg)) = msg {
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(180, msg.wind_direction);
+            assert_eq!(3.7, msg.wind_speed);
+            assert_eq!(6.0, msg.wind_gust);
+            assert_eq!(21.3, msg.temp);
+            assert_eq!(20.0, msg.chill);
+            assert_eq!(45, msg.humidity);
+            assert_eq!(HumidityStatus::Comfort, msg.humidity_status);
+            assert_eq!(3.0, msg.rain_rate);
+            assert_eq!(26.7, msg.rain_total);
+            assert_eq!(3.5, msg.uv);
+            assert_eq!(600, msg.solar);
+            assert_eq!(1013, msg.baro);
+            assert_eq!(Forecast::Sunny, msg.forecast);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected WeatherStation, got {:?}", msg);
+        }
+
+        let msg = parse_message(&[
+            0x76, 0x01, 0x00, 0x12, 0x34, 0x00, 0xB4, 0x00, 0x25, 0x00, 0x3C, 0x00, 0xD5, 0x00,
+            0xC8, 0x2D, 0x01, 0x01, 0x2C, 0x00, 0x01, 0x0B, 0x23, 0x02, 0x58, 0x03, 0xF5, 0x07,
+            0x00, 0x00, 0x69,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::WeatherStation(msg)) = msg {
+            assert_eq!(Forecast::Unknown(0x07), msg.forecast);
+        } else {
+            panic!("Expected WeatherStation, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_raw_pulses() {
+        let msg = parse_message(&[0x7F, 0x00, 0x00, 0x01, 0x5E, 0x04, 0x1A, 0x00, 0xC8]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::RawPulses(msg)) = msg {
+            assert_eq!(vec![350, 1050, 200], msg.pulses);
+        } else {
+            panic!("Expected RawPulses, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_undecoded() {
+        let msg = parse_message(&[0x03, 0x09, 0x00, 0xAA, 0x55, 0x01]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Undecoded(msg)) = msg {
+            assert_eq!(UndecodedProtocol::Oregon3, msg.protocol);
+            assert_eq!(vec![0xAA, 0x55, 0x01], msg.data);
+        } else {
+            panic!("Expected Undecoded, got {:?}", msg);
+        }
+
+        let msg = parse_message(&[0x03, 0x40, 0x00, 0xAA, 0x55]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::Undecoded(msg)) = msg {
+            assert_eq!(UndecodedProtocol::Unknown(0x40), msg.protocol);
+            assert_eq!(vec![0xAA, 0x55], msg.data);
+        } else {
+            panic!("Expected Undecoded, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_temp_hum() {
+        let msg =
+            parse_message(&[0x52, 0x05, 0x00, 0x12, 0x34, 0x80, 0x2D, 0x2D, 0x02, 0x89]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHum(msg)) = msg {
+            assert_eq!(TempHumModel::Wtgr800, msg.model);
+            assert_eq!(0x1234, msg.id);
+            assert_eq!(-4.5, msg.temp);
+            assert_eq!(45, msg.humidity);
+            assert_eq!(HumidityStatus::Dry, msg.humidity_status);
+            assert_eq!(BatteryStatus::Ok, msg.battery);
+            assert_eq!(8, msg.rssi);
+        } else {
+            panic!("Expected TempHum, got {:?}", msg);
+        }
+
+        // Unknown model, battery level 3
+        let msg =
+            parse_message(&[0x52, 0x20, 0x00, 0x12, 0x34, 0x00, 0xD5, 0x2D, 0x01, 0x63]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHum(msg)) = msg {
+            assert_eq!(TempHumModel::Unknown(0x20), msg.model);
+            assert_eq!(21.3, msg.temp);
+            assert_eq!(45, msg.humidity);
+            assert_eq!(BatteryStatus::Level(3), msg.battery);
+            assert_eq!(6, msg.rssi);
+        } else {
+            panic!("Expected TempHum, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn create_raw_packet() {
+        assert_eq!(
+            vec![0x05, 0x40, 0x00, 0x07, 0xAA, 0xBB],
+            raw_packet(0x07, 0x40, 0x00, &[0xAA, 0xBB]).unwrap()
+        );
+        assert_eq!(256, raw_packet(0, 0, 0, &[0; 252]).unwrap().len());
+        assert!(raw_packet(0, 0, 0, &[0; 253]).is_err());
+    }
+
+    #[test]
+    fn create_lighting1() {
+        let cmd = lighting1(0x05, Lighting1SubType::Arc, 'B', 5, Lighting1Command::On).unwrap();
+        assert_eq!(vec![0x07, 0x10, 0x01, 0x05, 0x42, 0x05, 0x01, 0x00], cmd);
+        assert!(lighting1(0, Lighting1SubType::X10, 'Q', 1, Lighting1Command::On).is_err());
+        assert!(lighting1(0, Lighting1SubType::X10, 'A', 0, Lighting1Command::On).is_err());
+    }
+
+    #[test]
+    fn create_lighting2() {
+        let cmd = lighting2(
+            0x06,
+            Lighting2SubType::Ac,
+            0x1234567,
+            10,
+            Lighting2Command::SetLevel,
+            15,
         )
-        .to_vec();
+        .unwrap();
         assert_eq!(
-            vec![0x0d, 00, 00, 0x12, 03, 0x53, 00, 00, 00, 01, 00, 00, 00, 00],
+            vec![0x0B, 0x11, 0x00, 0x06, 0x01, 0x23, 0x45, 0x67, 0x0A, 0x02, 0x0F, 0x00],
             cmd
         );
+        assert!(lighting2(
+            0,
+            Lighting2SubType::Ac,
+            0x4000000,
+            1,
+            Lighting2Command::On,
+            0
+        )
+        .is_err());
+        assert!(lighting2(
+            0,
+            Lighting2SubType::Ac,
+            1,
+            1,
+            Lighting2Command::SetLevel,
+            16
+        )
+        .is_err());
     }
 
     #[test]
-    fn set_mode_mixed() {
-        let cmd = set_mode(
+    fn create_lighting3() {
+        let cmd = lighting3(0x07, 3, 0b10_0000_0101, Lighting3Command::Level5).unwrap();
+        assert_eq!(
+            vec![0x08, 0x12, 0x00, 0x07, 0x02, 0x05, 0x02, 0x15, 0x00],
+            cmd
+        );
+        assert!(lighting3(0, 0, 1, Lighting3Command::On).is_err());
+        assert!(lighting3(0, 1, 0x400, Lighting3Command::On).is_err());
+    }
+
+    #[test]
+    fn create_lighting5() {
+        let cmd = lighting5(0x08, Lighting5SubType::LightwaveRf, 0xF12345, 2, 0x10, 0x1F).unwrap();
+        assert_eq!(
+            vec![0x0A, 0x14, 0x00, 0x08, 0xF1, 0x23, 0x45, 0x02, 0x10, 0x1F, 0x00],
+            cmd
+        );
+        assert!(lighting5(0, Lighting5SubType::Livolo, 0x1000000, 1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn create_lighting6() {
+        let cmd = lighting6(
+            0x09,
+            Lighting6SubType::Blyss,
+            0x1234,
+            'C',
+            2,
+            Lighting6Command::Off,
+            4,
+            0x80,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![0x0B, 0x15, 0x00, 0x09, 0x12, 0x34, 0x43, 0x02, 0x01, 0x04, 0x80, 0x00],
+            cmd
+        );
+        assert!(lighting6(
+            0,
+            Lighting6SubType::Blyss,
+            1,
+            'A',
+            6,
+            Lighting6Command::On,
+            0,
+            0
+        )
+        .is_err());
+        assert!(lighting6(
+            0,
+            Lighting6SubType::Blyss,
+            0x10000,
+            'A',
+            1,
+            Lighting6Command::On,
+            0,
+            0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn create_chime() {
+        let cmd = chime(0x0A, ChimeSubType::ByronSx, 0x00FF, 0x05).unwrap();
+        assert_eq!(vec![0x07, 0x16, 0x00, 0x0A, 0x00, 0xFF, 0x05, 0x00], cmd);
+        assert!(chime(0, ChimeSubType::ByronSx, 0x10000, 0x05).is_err());
+    }
+
+    #[test]
+    fn create_rfy() {
+        let cmd = rfy(0x0B, RfySubType::Rfy, 0x0A1234, 1, RfyCommand::Down).unwrap();
+        assert_eq!(
+            vec![0x0C, 0x1A, 0x00, 0x0B, 0x0A, 0x12, 0x34, 0x01, 0x03, 0x00, 0x00, 0x00, 0x00],
+            cmd
+        );
+        assert!(rfy(0, RfySubType::Rfy, 0x100000, 1, RfyCommand::Up).is_err());
+        assert!(rfy(0, RfySubType::Rfy, 0x0A1234, 5, RfyCommand::Up).is_err());
+        assert!(rfy(0, RfySubType::RfyExt, 0x0A1234, 5, RfyCommand::Up).is_ok());
+        assert!(rfy(0, RfySubType::RfyExt, 0x0A1234, 16, RfyCommand::Up).is_err());
+    }
+
+    #[test]
+    fn create_home_confort() {
+        let cmd = home_confort(0x0C, 0x063456, 'C', 4, HomeConfortCommand::On).unwrap();
+        assert_eq!(
+            vec![0x0C, 0x1B, 0x00, 0x0C, 0x06, 0x34, 0x56, 0x43, 0x04, 0x01, 0x00, 0x00, 0x00],
+            cmd
+        );
+        assert!(home_confort(0, 1, 'E', 1, HomeConfortCommand::On).is_err());
+    }
+
+    #[test]
+    fn create_hunter_fan() {
+        let cmd = hunter_fan(0x0D, [1, 2, 3, 4, 5, 6], HunterFanCommand::Light);
+        assert_eq!(
+            vec![0x0B, 0x1F, 0x00, 0x0D, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x02, 0x00],
+            cmd
+        );
+    }
+
+    #[test]
+    fn create_security1() {
+        let cmd = security1(
+            0x0E,
+            Security1SubType::X10Remote,
+            0x123456,
+            Security1Status::ArmAway,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![0x08, 0x20, 0x02, 0x0E, 0x12, 0x34, 0x56, 0x09, 0x00],
+            cmd
+        );
+    }
+
+    #[test]
+    fn create_camera() {
+        let cmd = camera(0x0F, 'D', CameraCommand::Sweep).unwrap();
+        assert_eq!(vec![0x06, 0x28, 0x00, 0x0F, 0x44, 0x0E, 0x00], cmd);
+        assert!(camera(0, 'Z', CameraCommand::Left).is_err());
+    }
+
+    #[test]
+    fn create_remote() {
+        let cmd = remote(0x10, RemoteSubType::Medion, 0x0F, 0x12, true).unwrap();
+        assert_eq!(vec![0x06, 0x30, 0x02, 0x10, 0x0F, 0x12, 0x08], cmd);
+        assert!(remote(0, RemoteSubType::Medion, 0x100, 0x12, true).is_err());
+    }
+
+    #[test]
+    fn create_thermostat1() {
+        let cmd = thermostat1(
+            0x11,
+            Thermostat1SubType::Digimax,
+            0x1234,
+            19,
+            21,
+            Thermostat1Status::Demand,
+            Thermostat1Mode::Cooling,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![0x09, 0x40, 0x00, 0x11, 0x12, 0x34, 19, 21, 0x81, 0x00],
+            cmd
+        );
+        assert!(thermostat1(
+            0,
+            Thermostat1SubType::Digimax,
+            1,
+            20,
+            50,
+            Thermostat1Status::Demand,
+            Thermostat1Mode::Heating,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn create_thermostat3() {
+        let cmd = thermostat3(
             0x12,
-            Default::default(),
-            Protocols1::IMAGINTRONIX | Protocols1::RUBICSON,
-            Protocols2::LEGRAND | Protocols2::MERTIK,
-            Protocols3::X10 | Protocols3::ATI,
-            Protocols4::KEELOQ,
+            Thermostat3SubType::MertikG6rH4tb,
+            0x123456,
+            Thermostat3Command::RunDown,
         )
-        .to_vec();
+        .unwrap();
         assert_eq!(
-            vec![0x0d, 00, 00, 0x12, 03, 0x53, 00, 0x42, 0x11, 0x41, 0x01, 00, 00, 00],
+            vec![0x08, 0x42, 0x01, 0x12, 0x12, 0x34, 0x56, 0x05, 0x00],
             cmd
         );
     }
 
     #[test]
-    fn save_cmd() {
-        let cmd = super::save(3).to_vec();
-        assert_eq!(vec![0x0d, 0, 0, 3, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0], cmd);
+    fn create_raw_pulses() {
+        let frames = raw_pulses(0xFE, &[350, 1050], 3).unwrap();
+        assert_eq!(
+            vec![vec![0x08, 0x7F, 0x00, 0xFE, 0x03, 0x01, 0x5E, 0x04, 0x1A]],
+            frames
+        );
+
+        // 0xFF is reserved for internal commands
+        let pulses = vec![100; 130];
+        let frames = raw_pulses(0xFE, &pulses, 2).unwrap();
+        assert_eq!(2, frames.len());
+        assert_eq!([0xFC, 0x7F, 0x00, 0xFE, 0x00], frames[0][..5]);
+        assert_eq!([0x10, 0x7F, 0x01, 0x00, 0x02], frames[1][..5]);
+
+        assert!(raw_pulses(0, &[], 1).is_err());
+        assert!(raw_pulses(0, &[1; 497], 1).is_err());
+        assert!(raw_pulses(0, &[1], 0).is_err());
+    }
+
+    #[test]
+    fn create_async_port() {
+        let cmd = async_port(
+            0x13,
+            &AsyncPort {
+                command: AsyncPortCommand::ReceiveRaw,
+                baud_rate: AsyncBaudRate::Baud9600,
+                parity: AsyncParity::Even,
+                data_bits: 7,
+                stop_bits: 1,
+                inverted: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            vec![0x0B, 0x61, 0x01, 0x13, 0xFE, 0x06, 0x02, 0x07, 0x01, 0x01, 0x00, 0x00],
+            cmd
+        );
+    }
+
+    #[test]
+    fn create_async_data() {
+        let frames = async_data(0x14, b"hello").unwrap();
+        assert_eq!(
+            vec![vec![0x08, 0x62, 0x03, 0x14, b'h', b'e', b'l', b'l', b'o']],
+            frames
+        );
+        let frames = async_data(0xFE, &[0; 300]).unwrap();
+        assert_eq!(2, frames.len());
+        assert_eq!([0xFF, 0x62, 0x03, 0xFE], frames[0][..4]);
+        // 0xFF is reserved for internal commands
+        assert_eq!([0x33, 0x62, 0x03, 0x00], frames[1][..4]);
+    }
+
+    #[test]
+    fn parse_transmit_responses() {
+        let msg = parse_message(&[0x02, 0x01, 0x05, 0x01]).unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::TransmitResponse(r)) = msg {
+            assert_eq!(Ok(TransmitResult::AckDelayed), r);
+        } else {
+            panic!("Expected TransmitResponse, got {:?}", msg);
+        }
+        let msg = parse_message(&[0x02, 0x01, 0x05, 0x03]).unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::TransmitResponse(r)) = msg {
+            assert_eq!(Err(TransmitError::InvalidAcAddress), r);
+        } else {
+            panic!("Expected TransmitResponse, got {:?}", msg);
+        }
+        let msg = parse_message(&[0x02, 0x00, 0x05, 0x00]).unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::TransmitResponse(r)) = msg {
+            assert_eq!(Err(TransmitError::ReceiverDidNotLock), r);
+        } else {
+            panic!("Expected TransmitResponse, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn parse_rfy_remote_list() {
+        let msg = parse_message(&[0x01, 0x03, 0x05, 0x02, 0x0A, 0x12, 0x34, 0x01, 0x00]).unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::RfyRemoteList(remote)) = msg {
+            assert_eq!(2, remote.location);
+            assert_eq!(0x0A1234, remote.id);
+            assert_eq!(1, remote.unit_code);
+        } else {
+            panic!("Expected RfyRemoteList, got {:?}", msg);
+        }
+        let msg = parse_message(&[0x01, 0x01, 0x05, 0x00]).unwrap();
+        assert!(matches!(
+            msg,
+            ReceivedCommand::InterfaceMessage(InterfaceMessage::UnknownRfyRemote)
+        ));
+    }
+
+    #[test]
+    fn parse_asa_remote_list() {
+        let msg = parse_message(&[0x01, 0x04, 0x05, 0x00, 0x01, 0x02, 0x03, 0x05, 0x00]).unwrap();
+        if let ReceivedCommand::InterfaceMessage(InterfaceMessage::AsaRemoteList(remote)) = msg {
+            assert_eq!(0, remote.location);
+            assert_eq!(0x010203, remote.id);
+            assert_eq!(5, remote.unit_code);
+        } else {
+            panic!("Expected AsaRemoteList, got {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn humidity_status() {
+        assert_eq!(HumidityStatus::Wet, HumidityStatus::from(0x03));
+        assert_eq!(HumidityStatus::Unknown(0x07), HumidityStatus::from(0x07));
+        assert_eq!(0x07, u8::from(HumidityStatus::Unknown(0x07)));
+    }
+
+    #[test]
+    fn battery_status() {
+        assert_eq!(BatteryStatus::Low, BatteryStatus::from(0x60));
+        assert!(BatteryStatus::from(0x00).is_low());
+        assert_eq!(BatteryStatus::Level(4), BatteryStatus::from(0x04));
+        assert_eq!(50, BatteryStatus::from(0x04).percentage());
+        assert!(!BatteryStatus::from(0x04).is_low());
+        assert_eq!(100, BatteryStatus::from(0x0F).percentage());
+        assert_eq!(100, BatteryStatus::Level(200).percentage());
+    }
+
+    #[test]
+    fn rssi() {
+        assert_eq!(-100, Rssi::from(0).as_dbm());
+        assert_eq!(-40, Rssi::from(15).as_dbm());
+        assert_eq!(SignalQuality::NoSignal, Rssi::from(0).quality());
+        assert_eq!(SignalQuality::Weak, Rssi::from(3).quality());
+        assert_eq!(SignalQuality::Good, Rssi::from(7).quality());
+        assert_eq!(SignalQuality::Excellent, Rssi::from(12).quality());
+    }
+
+    #[test]
+    fn parse_not_parsed() {
+        let msg = parse_message(&[0x5E, 0x01, 0x07, 0x12, 0x34]).unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::NotParsed { header, data }) = msg {
+            assert_eq!(PacketType::GAS, header.packet_type());
+            assert_eq!(0x01, header.sub_type());
+            assert_eq!(0x07, header.seqnbr());
+            assert_eq!(PacketHeader::parse(&[0x5E, 0x01, 0x07]).unwrap().0, header);
+            assert_eq!(vec![0x12, 0x34], data);
+        } else {
+            panic!("Expected NotParsed, got {:?}", msg);
+        }
+
+        // Unknown sub type and command of a known packet type
+        for frame in [
+            [0x10, 0x7E, 0x00, 0x42, 0x05, 0x01, 0x70],
+            [0x10, 0x01, 0x00, 0x42, 0x05, 0x7E, 0x70],
+        ] {
+            let msg = parse_message(&frame).unwrap();
+            if let ReceivedCommand::ProtocolMessage(ProtocolMessage::NotParsed { header, data }) =
+                msg
+            {
+                assert_eq!(PacketType::Lighting1, header.packet_type());
+                assert_eq!(frame[3..].to_vec(), data);
+            } else {
+                panic!("Expected NotParsed, got {:?}", msg);
+            }
+        }
     }
 }
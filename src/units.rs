@@ -0,0 +1,126 @@
+//! Measurements of the sensor messages as typed quantities, so units and scales
+//! can't be mixed up. The raw fields are in Celsius, hPa, m/s and mm.
+use crate::trx_command::{Bbq, Temp, TempHum, TempHumBaro, TempRain, WeatherStation};
+use uom::si::{
+    f32::{Length, Pressure, ThermodynamicTemperature, Velocity},
+    length::millimeter,
+    pressure::hectopascal,
+    thermodynamic_temperature::degree_celsius,
+    velocity::meter_per_second,
+};
+
+fn celsius(temp: f32) -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<degree_celsius>(temp)
+}
+
+fn hpa(baro: u16) -> Pressure {
+    Pressure::new::<hectopascal>(baro as f32)
+}
+
+impl TempHum {
+    /// Temperature
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.temp)
+    }
+}
+
+impl Temp {
+    /// Temperature
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.temp)
+    }
+}
+
+impl TempHumBaro {
+    /// Temperature
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.temp)
+    }
+
+    /// Barometric pressure
+    pub fn pressure(&self) -> Pressure {
+        hpa(self.baro)
+    }
+}
+
+impl TempRain {
+    /// Temperature
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.temp)
+    }
+
+    /// Total rain
+    pub fn rainfall(&self) -> Length {
+        Length::new::<millimeter>(self.rain_total)
+    }
+}
+
+impl Bbq {
+    /// Food probe temperature
+    pub fn food_temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.food_temp)
+    }
+
+    /// BBQ probe temperature
+    pub fn bbq_temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.bbq_temp)
+    }
+}
+
+impl WeatherStation {
+    /// Temperature
+    pub fn temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.temp)
+    }
+
+    /// Wind chill
+    pub fn chill_temperature(&self) -> ThermodynamicTemperature {
+        celsius(self.chill)
+    }
+
+    /// Average wind speed
+    pub fn wind_velocity(&self) -> Velocity {
+        Velocity::new::<meter_per_second>(self.wind_speed)
+    }
+
+    /// Wind gust
+    pub fn gust_velocity(&self) -> Velocity {
+        Velocity::new::<meter_per_second>(self.wind_gust)
+    }
+
+    /// Rain rate, as the height of rain falling per time
+    pub fn rain_intensity(&self) -> Velocity {
+        // mm/h
+        Velocity::new::<meter_per_second>(self.rain_rate / 3_600_000.0)
+    }
+
+    /// Total rain
+    pub fn rainfall(&self) -> Length {
+        Length::new::<millimeter>(self.rain_total)
+    }
+
+    /// Barometric pressure
+    pub fn pressure(&self) -> Pressure {
+        hpa(self.baro)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::trx_command::{parse_message, ProtocolMessage, ReceivedCommand};
+    use uom::si::{pressure::kilopascal, thermodynamic_temperature::kelvin};
+
+    #[test]
+    fn temp_hum_baro_units() {
+        let msg = parse_message(&[
+            0x54, 0x01, 0x00, 0x12, 0x34, 0x00, 0xD5, 0x2D, 0x01, 0x03, 0xF5, 0x02, 0x69,
+        ])
+        .unwrap();
+        if let ReceivedCommand::ProtocolMessage(ProtocolMessage::TempHumBaro(msg)) = msg {
+            assert!((msg.temperature().get::<kelvin>() - 294.45).abs() < 0.01);
+            assert!((msg.pressure().get::<kilopascal>() - 101.3).abs() < 0.01);
+        } else {
+            panic!("Expected TempHumBaro, got {:?}", msg);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Values derived from the weather sensor measurements, temperatures are in Celsius.
+use crate::trx_command::{celsius_to_fahrenheit, TempHum, TempHumBaro, WeatherStation};
+
+/// Dew point in Celsius, from the temperature in Celsius and relative humidity in
+/// percent. Uses the Magnus formula, accurate to 0.1 °C for -45 - 60 °C.
+pub fn dew_point(temp: f32, humidity: u8) -> f32 {
+    const A: f32 = 17.62;
+    const B: f32 = 243.12;
+    // The formula is undefined at 0%
+    let humidity = humidity.clamp(1, 100) as f32;
+    let gamma = (humidity / 100.0).ln() + A * temp / (B + temp);
+    B * gamma / (A - gamma)
+}
+
+/// Wind chill in Celsius, from the temperature in Celsius and wind speed in m/s.
+/// Uses the formula of the North American and UK weather services, which is only
+/// defined at or below 10 °C and above 4.8 km/h, otherwise `temp` is returned.
+pub fn wind_chill(temp: f32, wind_speed: f32) -> f32 {
+    let wind_kmh = wind_speed * 3.6;
+    if temp > 10.0 || wind_kmh <= 4.8 {
+        return temp;
+    }
+    let v = wind_kmh.powf(0.16);
+    13.12 + 0.6215 * temp - 11.37 * v + 0.3965 * temp * v
+}
+
+/// Heat index, or apparent temperature, in Celsius from the temperature in Celsius
+/// and relative humidity in percent. Uses the US National Weather Service algorithm.
+pub fn heat_index(temp: f32, humidity: u8) -> f32 {
+    let t = celsius_to_fahrenheit(temp);
+    let rh = humidity.min(100) as f32;
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    let hi = if (simple + t) / 2.0 < 80.0 {
+        simple
+    } else {
+        let hi = -42.379 + 2.049_015_2 * t + 10.143_331 * rh
+            - 0.224_755_4 * t * rh
+            - 0.006_837_83 * t * t
+            - 0.054_817_17 * rh * rh
+            + 0.001_228_74 * t * t * rh
+            + 0.000_852_82 * t * rh * rh
+            - 0.000_001_99 * t * t * rh * rh;
+        if rh < 13.0 && (80.0..=112.0).contains(&t) {
+            hi - (13.0 - rh) / 4.0 * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt()
+        } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+            hi + (rh - 85.0) / 10.0 * ((87.0 - t) / 5.0)
+        } else {
+            hi
+        }
+    };
+    (hi - 32.0) * 5.0 / 9.0
+}
+
+impl TempHum {
+    /// Dew point in Celsius, see `dew_point`
+    pub fn dew_point(&self) -> f32 {
+        dew_point(self.temp, self.humidity)
+    }
+
+    /// Heat index in Celsius, see `heat_index`
+    pub fn heat_index(&self) -> f32 {
+        heat_index(self.temp, self.humidity)
+    }
+}
+
+impl TempHumBaro {
+    /// Dew point in Celsius, see `dew_point`
+    pub fn dew_point(&self) -> f32 {
+        dew_point(self.temp, self.humidity)
+    }
+
+    /// Heat index in Celsius, see `heat_index`
+    pub fn heat_index(&self) -> f32 {
+        heat_index(self.temp, self.humidity)
+    }
+}
+
+impl WeatherStation {
+    /// Dew point in Celsius, see `dew_point`
+    pub fn dew_point(&self) -> f32 {
+        dew_point(self.temp, self.humidity)
+    }
+
+    /// Heat index in Celsius, see `heat_index`
+    pub fn heat_index(&self) -> f32 {
+        heat_index(self.temp, self.humidity)
+    }
+
+    /// Wind chill in Celsius computed from the temperature and average wind speed,
+    /// see `wind_chill`. Not all stations report `chill` themselves.
+    pub fn wind_chill(&self) -> f32 {
+        wind_chill(self.temp, self.wind_speed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_near(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.1,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn derived_values() {
+        assert_near(9.3, dew_point(20.0, 50));
+        assert!(dew_point(20.0, 0).is_finite());
+        assert_near(-17.9, wind_chill(-10.0, 20.0 / 3.6));
+        assert_near(15.0, wind_chill(15.0, 10.0));
+        assert_near(40.4, heat_index(32.0, 70));
+        assert_near(19.4, heat_index(20.0, 50));
+    }
+}